@@ -1,20 +1,37 @@
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, String, Symbol, Val, Vec};
 
-use crate::core::{DisputeManager, EscrowManager, MilestoneManager};
+use crate::core::{
+    AdminManager, DisputeManager, EscrowManager, HistoryManager, MilestoneManager, RoleManager,
+};
 use crate::error::ContractError;
 use crate::events::handler::{
-    ChgEsc, DisEsc, DisputeResolved, EscrowDisputed, ExtTtlEvt, FundEsc, InitEsc,
-    MilestoneApproved, MilestoneStatusChanged,
+    AdminChanged, AdminProposed, AmountSet, ChgEsc, DisEsc, DisputePayout, DisputeResolved,
+    EscrowDisputed, EscrowPauseChanged, ExtTtlEvt, FeeCollectorSet, FundEsc, InitEsc,
+    MaxBatchSizeSet, MaxPlatformFeeSet, MilestoneAdded, MilestoneApproved,
+    MilestoneDescriptionChanged, MilestoneRemoved, MilestoneStatusChanged, MinReceiverShareSet,
+    PlatformFeeDiscountChanged, ProtocolFeeSet, ProtocolPauseChanged, ReleasePayout,
+    RoleChangeProposed, RoleChanged, TitleDescriptionSet, TokenAllowlistChanged,
+    TokenAllowlistCleared, UpdateApproved, UpdateProposed, WasmAllowlistChanged,
+};
+use crate::storage::ttl::extend_ttl_page;
+use crate::storage::types::{
+    AddressBalance, AuditEntry, Escrow, EscrowStatus, EscrowSummary, EscrowSummaryEntry,
+    Governance, LegFunding, Milestone, PayoutBreakdown, Role, RoleChangeProposal, UpdateProposal,
 };
-use crate::storage::types::{AddressBalance, Escrow};
 
 #[contract]
 pub struct EscrowContract;
 
 #[contractimpl]
 impl EscrowContract {
-    pub fn __constructor() {}
+    pub fn __constructor(e: Env, governance: Governance) {
+        AdminManager::set_governance(&e, &governance);
+    }
 
+    /// Deploys an escrow governed by this deployment's governance contract, which the
+    /// constructor is always given. `wasm_hash` must be on the governance contract's wasm
+    /// allowlist, which starts empty, so the admin has to allow a hash before the first
+    /// deployment.
     pub fn deploy(
         env: Env,
         deployer: Address,
@@ -22,29 +39,42 @@ impl EscrowContract {
         salt: BytesN<32>,
         init_fn: Symbol,
         init_args: Vec<Val>,
-        constructor_args: Vec<Val>,
-    ) -> (Address, Val) {
+    ) -> Result<(Address, Val), ContractError> {
         if deployer != env.current_contract_address() {
             deployer.require_auth();
         }
 
+        if !AdminManager::is_wasm_hash_allowed(&env, &wasm_hash) {
+            return Err(ContractError::IncompatibleEscrowWasmHash);
+        }
+
+        let governance = Governance::Contract(AdminManager::get_governance_root(&env));
         let deployed_address = env
             .deployer()
             .with_address(deployer, salt)
-            .deploy_v2(wasm_hash, constructor_args);
+            .deploy_v2(wasm_hash, (governance,));
 
         let res: Val = env.invoke_contract(&deployed_address, &init_fn, init_args);
-        (deployed_address, res)
+        Ok((deployed_address, res))
     }
 
     ////////////////////////
     // Escrow /////
     ////////////////////////
 
-    pub fn initialize_escrow(e: &Env, escrow_properties: Escrow) -> Result<Escrow, ContractError> {
-        let initialized_escrow = EscrowManager::initialize_escrow(e, escrow_properties)?;
+    pub fn initialize_escrow(
+        e: &Env,
+        escrow_properties: Escrow,
+        milestones: Vec<Milestone>,
+    ) -> Result<Escrow, ContractError> {
+        let initialized_escrow =
+            EscrowManager::initialize_escrow(e, escrow_properties, milestones)?;
         InitEsc {
-            escrow: initialized_escrow.clone(),
+            engagement_id: initialized_escrow.engagement_id.clone(),
+            platform: initialized_escrow.roles.platform_address.clone(),
+            amount: initialized_escrow.amount,
+            token: initialized_escrow.trustline.address.clone(),
+            milestone_count: MilestoneManager::get_milestone_count(e),
         }
         .publish(e);
         Ok(initialized_escrow)
@@ -54,10 +84,17 @@ impl EscrowContract {
         e: &Env,
         signer: Address,
         expected_escrow: Escrow,
+        token: Address,
         amount: i128,
     ) -> Result<(), ContractError> {
-        EscrowManager::fund_escrow(e, &signer, &expected_escrow, amount)?;
-        FundEsc { signer, amount }.publish(e);
+        EscrowManager::fund_escrow(e, &signer, &expected_escrow, &token, amount)?;
+        FundEsc {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            signer,
+            token,
+            amount,
+        }
+        .publish(e);
         Ok(())
     }
 
@@ -66,27 +103,118 @@ impl EscrowContract {
         release_signer: Address,
         trustless_work_address: Address,
     ) -> Result<(), ContractError> {
-        EscrowManager::release_funds(e, &release_signer, &trustless_work_address)?;
-        DisEsc { release_signer }.publish(e);
+        let (escrow, breakdowns) =
+            EscrowManager::release_funds(e, &release_signer, &trustless_work_address)?;
+        DisEsc {
+            engagement_id: escrow.engagement_id.clone(),
+            release_signer: release_signer.clone(),
+        }
+        .publish(e);
+
+        for breakdown in breakdowns.iter() {
+            ReleasePayout {
+                engagement_id: escrow.engagement_id.clone(),
+                release_signer: release_signer.clone(),
+                token: breakdown.token,
+                trustless_work_address: trustless_work_address.clone(),
+                trustless_work_fee: breakdown.trustless_work_fee,
+                platform_address: escrow.roles.platform_address.clone(),
+                platform_fee: breakdown.platform_fee,
+                payouts: breakdown.payouts,
+            }
+            .publish(e);
+        }
         Ok(())
     }
 
+    pub fn preview_release(e: &Env) -> Result<Vec<PayoutBreakdown>, ContractError> {
+        EscrowManager::preview_release(e)
+    }
+
     pub fn update_escrow(
         e: &Env,
         plataform_address: Address,
         escrow_properties: Escrow,
-    ) -> Result<Escrow, ContractError> {
-        let updated_escrow = EscrowManager::change_escrow_properties(
+    ) -> Result<Option<UpdateProposal>, ContractError> {
+        let proposal = EscrowManager::change_escrow_properties(
             e,
             &plataform_address,
             escrow_properties.clone(),
         )?;
-        ChgEsc {
-            platform: plataform_address,
-            engagement_id: escrow_properties.engagement_id.clone(),
+        match &proposal {
+            Some(proposal) => UpdateProposed {
+                platform: plataform_address,
+                engagement_id: escrow_properties.engagement_id.clone(),
+                required_approvals: proposal.required_approvals.clone(),
+                expiration_ledger: proposal.expiration_ledger,
+            }
+            .publish(e),
+            None => ChgEsc {
+                platform: plataform_address,
+                engagement_id: escrow_properties.engagement_id.clone(),
+            }
+            .publish(e),
+        }
+        Ok(proposal)
+    }
+
+    pub fn approve_escrow_update(e: &Env, signer: Address) -> Result<(), ContractError> {
+        let (proposal, applied_escrow) = EscrowManager::approve_escrow_update(e, &signer)?;
+        UpdateApproved {
+            signer,
+            engagement_id: proposal.escrow.engagement_id.clone(),
+        }
+        .publish(e);
+        if let Some(escrow) = applied_escrow {
+            ChgEsc {
+                platform: proposal.proposer,
+                engagement_id: escrow.engagement_id,
+            }
+            .publish(e);
+        }
+        Ok(())
+    }
+
+    pub fn get_pending_update(e: &Env) -> Option<UpdateProposal> {
+        EscrowManager::get_pending_update(e)
+    }
+
+    pub fn set_title_description(
+        e: &Env,
+        platform_address: Address,
+        title: String,
+        description: String,
+    ) -> Result<(), ContractError> {
+        let (previous_escrow, escrow) =
+            EscrowManager::set_title_description(e, &platform_address, title, description)?;
+        TitleDescriptionSet {
+            engagement_id: escrow.engagement_id,
+            platform: platform_address,
+            old_title: previous_escrow.title,
+            new_title: escrow.title,
+            old_description: previous_escrow.description,
+            new_description: escrow.description,
         }
         .publish(e);
-        Ok(updated_escrow)
+        Ok(())
+    }
+
+    pub fn set_amount(
+        e: &Env,
+        platform_address: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        let (old_amount, escrow) = EscrowManager::set_amount(e, &platform_address, &token, amount)?;
+        AmountSet {
+            engagement_id: escrow.engagement_id,
+            platform: platform_address,
+            old_amount,
+            new_amount: amount,
+            token,
+        }
+        .publish(e);
+        Ok(())
     }
 
     pub fn get_escrow(e: &Env) -> Result<Escrow, ContractError> {
@@ -107,36 +235,338 @@ impl EscrowContract {
         EscrowManager::get_multiple_escrow_balances(e, addresses)
     }
 
+    pub fn get_status(e: &Env) -> Result<EscrowStatus, ContractError> {
+        EscrowManager::get_status(e)
+    }
+
+    pub fn get_required_funding(e: &Env) -> Result<Vec<LegFunding>, ContractError> {
+        EscrowManager::get_required_funding(e)
+    }
+
+    pub fn get_history(e: &Env, offset: u32, limit: u32) -> Vec<AuditEntry> {
+        HistoryManager::get_history(e, offset, limit)
+    }
+
+    pub fn get_history_count(e: &Env) -> u32 {
+        HistoryManager::get_history_count(e)
+    }
+
+    pub fn get_first_history_index(e: &Env) -> u32 {
+        HistoryManager::get_first_history_index(e)
+    }
+
+    pub fn get_escrow_summary(e: &Env) -> Result<EscrowSummary, ContractError> {
+        EscrowManager::get_escrow_summary(e)
+    }
+
+    pub fn get_escrow_summaries(
+        e: &Env,
+        addresses: Vec<Address>,
+    ) -> Result<Vec<EscrowSummaryEntry>, ContractError> {
+        EscrowManager::get_escrow_summaries(e, addresses)
+    }
+
     ////////////////////////
     // Admin / TTL /////
     ////////////////////////
 
+    /// Extends the instance and one page of persistent escrow entries, milestones first and
+    /// then history. Returns the offset of the next page, or `None` once the whole footprint
+    /// is covered. Open to any signer: TTL extension can't change escrow state and is
+    /// permissionless at the network level anyway.
     pub fn extend_contract_ttl(
         e: &Env,
-        platform_address: Address,
+        signer: Address,
         ledgers_to_extend: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Option<u32>, ContractError> {
+        signer.require_auth();
+        EscrowManager::get_escrow(e)?;
+
+        let next_offset = extend_ttl_page(e, ledgers_to_extend, ledgers_to_extend, offset, limit);
+
+        ExtTtlEvt {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            signer,
+            ledgers_to_extend,
+        }
+        .publish(e);
+
+        Ok(next_offset)
+    }
+
+    pub fn propose_admin(e: &Env, admin: Address, new_admin: Address) -> Result<(), ContractError> {
+        AdminManager::propose_admin(e, &admin, &new_admin)?;
+        AdminProposed {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            new_admin,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn accept_admin(e: &Env, new_admin: Address) -> Result<(), ContractError> {
+        let previous_admin = AdminManager::accept_admin(e, &new_admin)?;
+        AdminChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            previous_admin,
+            new_admin,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn set_protocol_fee(e: &Env, admin: Address, fee_bps: u32) -> Result<(), ContractError> {
+        AdminManager::set_protocol_fee(e, &admin, fee_bps)?;
+        ProtocolFeeSet {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            fee_bps,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn set_platform_fee_discount(
+        e: &Env,
+        admin: Address,
+        platform: Address,
+        fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        AdminManager::set_platform_fee_discount(e, &admin, &platform, fee_bps)?;
+        PlatformFeeDiscountChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            platform,
+            fee_bps,
+            active: true,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn remove_platform_fee_discount(
+        e: &Env,
+        admin: Address,
+        platform: Address,
+    ) -> Result<(), ContractError> {
+        AdminManager::remove_platform_fee_discount(e, &admin, &platform)?;
+        PlatformFeeDiscountChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            fee_bps: AdminManager::get_protocol_fee(e),
+            platform,
+            active: false,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn set_fee_collector(
+        e: &Env,
+        admin: Address,
+        fee_collector: Address,
     ) -> Result<(), ContractError> {
-        platform_address.require_auth();
+        AdminManager::set_fee_collector(e, &admin, &fee_collector)?;
+        FeeCollectorSet {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            fee_collector,
+        }
+        .publish(e);
+        Ok(())
+    }
 
-        let escrow = EscrowManager::get_escrow(e)?;
-        if platform_address != escrow.roles.platform_address {
-            return Err(ContractError::OnlyPlatformAddressExecuteThisFunction);
+    pub fn set_wasm_hash_allowed(
+        e: &Env,
+        admin: Address,
+        wasm_hash: BytesN<32>,
+        allowed: bool,
+    ) -> Result<Vec<BytesN<32>>, ContractError> {
+        let allowlist = AdminManager::set_wasm_hash_allowed(e, &admin, &wasm_hash, allowed)?;
+        WasmAllowlistChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            wasm_hash,
+            allowed,
         }
+        .publish(e);
+        Ok(allowlist)
+    }
 
-        let min_ledgers = 1u32;
-        e.storage()
-            .instance()
-            .extend_ttl(min_ledgers, ledgers_to_extend);
+    pub fn set_token_allowed(
+        e: &Env,
+        admin: Address,
+        platform: Address,
+        token: Address,
+        allowed: bool,
+    ) -> Result<Vec<Address>, ContractError> {
+        let allowlist = AdminManager::set_token_allowed(e, &admin, &platform, &token, allowed)?;
+        TokenAllowlistChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            platform,
+            token,
+            allowed,
+        }
+        .publish(e);
+        Ok(allowlist)
+    }
 
-        ExtTtlEvt {
-            platform: platform_address,
-            ledgers_to_extend,
+    pub fn clear_token_allowlist(
+        e: &Env,
+        admin: Address,
+        platform: Address,
+    ) -> Result<(), ContractError> {
+        AdminManager::clear_token_allowlist(e, &admin, &platform)?;
+        TokenAllowlistCleared {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            platform,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn set_protocol_paused(e: &Env, admin: Address, paused: bool) -> Result<(), ContractError> {
+        AdminManager::set_protocol_paused(e, &admin, paused)?;
+        ProtocolPauseChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            paused,
         }
         .publish(e);
+        Ok(())
+    }
 
+    pub fn set_max_batch_size(
+        e: &Env,
+        admin: Address,
+        max_batch_size: u32,
+    ) -> Result<(), ContractError> {
+        AdminManager::set_max_batch_size(e, &admin, max_batch_size)?;
+        MaxBatchSizeSet {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            max_batch_size,
+        }
+        .publish(e);
         Ok(())
     }
 
+    pub fn set_max_platform_fee_bps(
+        e: &Env,
+        admin: Address,
+        max_platform_fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        AdminManager::set_max_platform_fee_bps(e, &admin, max_platform_fee_bps)?;
+        MaxPlatformFeeSet {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            max_platform_fee_bps,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn set_min_receiver_share_bps(
+        e: &Env,
+        admin: Address,
+        min_receiver_share_bps: u32,
+    ) -> Result<(), ContractError> {
+        AdminManager::set_min_receiver_share_bps(e, &admin, min_receiver_share_bps)?;
+        MinReceiverShareSet {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            min_receiver_share_bps,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn pause_escrow(e: &Env, admin: Address) -> Result<(), ContractError> {
+        AdminManager::set_escrow_paused(e, &admin, true)?;
+        EscrowPauseChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            paused: true,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn unpause_escrow(e: &Env, admin: Address) -> Result<(), ContractError> {
+        AdminManager::set_escrow_paused(e, &admin, false)?;
+        EscrowPauseChanged {
+            engagement_id: EscrowManager::get_engagement_id(e),
+            admin,
+            paused: false,
+        }
+        .publish(e);
+        Ok(())
+    }
+
+    pub fn get_admin(e: &Env) -> Result<Address, ContractError> {
+        AdminManager::get_admin(e)
+    }
+
+    /// Contract the protocol settings are read from, `None` when this is the governance
+    /// contract itself.
+    pub fn get_governance(e: &Env) -> Option<Address> {
+        AdminManager::get_governance(e)
+    }
+
+    pub fn get_pending_admin(e: &Env) -> Option<Address> {
+        AdminManager::get_pending_admin(e)
+    }
+
+    pub fn get_protocol_fee(e: &Env) -> u32 {
+        AdminManager::get_protocol_fee(e)
+    }
+
+    pub fn get_platform_fee_discounts(e: &Env) -> Map<Address, u32> {
+        AdminManager::get_platform_fee_discounts(e)
+    }
+
+    /// Protocol fee bps charged on escrows of `platform`, discount included.
+    pub fn get_platform_protocol_fee(e: &Env, platform: Address) -> u32 {
+        AdminManager::get_platform_protocol_fee(e, &platform)
+    }
+
+    pub fn get_fee_collector(e: &Env) -> Option<Address> {
+        AdminManager::get_fee_collector(e)
+    }
+
+    pub fn get_wasm_allowlist(e: &Env) -> Vec<BytesN<32>> {
+        AdminManager::get_wasm_allowlist(e)
+    }
+
+    pub fn get_token_allowlist(e: &Env, platform: Address) -> Option<Vec<Address>> {
+        AdminManager::get_token_allowlist(e, &platform)
+    }
+
+    pub fn is_protocol_paused(e: &Env) -> bool {
+        AdminManager::is_protocol_paused(e)
+    }
+
+    pub fn is_escrow_paused(e: &Env) -> bool {
+        AdminManager::is_escrow_paused(e)
+    }
+
+    pub fn get_max_batch_size(e: &Env) -> u32 {
+        AdminManager::get_max_batch_size(e)
+    }
+
+    pub fn get_max_platform_fee_bps(e: &Env) -> u32 {
+        AdminManager::get_max_platform_fee_bps(e)
+    }
+
+    pub fn get_min_receiver_share_bps(e: &Env) -> u32 {
+        AdminManager::get_min_receiver_share_bps(e)
+    }
+
     ////////////////////////
     // Milestones /////
     ////////////////////////
@@ -148,14 +578,21 @@ impl EscrowContract {
         new_evidence: Option<String>,
         service_provider: Address,
     ) -> Result<(), ContractError> {
-        let escrow = MilestoneManager::change_milestone_status(
+        let (escrow, old_status) = MilestoneManager::change_milestone_status(
             &e,
             milestone_index,
-            new_status,
+            new_status.clone(),
             new_evidence,
-            service_provider,
+            service_provider.clone(),
         )?;
-        MilestoneStatusChanged { escrow }.publish(&e);
+        MilestoneStatusChanged {
+            engagement_id: escrow.engagement_id,
+            service_provider,
+            milestone_index: milestone_index as u32,
+            old_status,
+            new_status,
+        }
+        .publish(&e);
         Ok(())
     }
 
@@ -164,9 +601,107 @@ impl EscrowContract {
         milestone_index: i128,
         approver: Address,
     ) -> Result<(), ContractError> {
-        let escrow =
-            MilestoneManager::change_milestone_approved_flag(&e, milestone_index, approver)?;
-        MilestoneApproved { escrow }.publish(&e);
+        let escrow = MilestoneManager::change_milestone_approved_flag(
+            &e,
+            milestone_index,
+            approver.clone(),
+        )?;
+        MilestoneApproved {
+            engagement_id: escrow.engagement_id,
+            approver,
+            milestone_index: milestone_index as u32,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    pub fn get_milestone(e: Env, milestone_index: i128) -> Result<Milestone, ContractError> {
+        MilestoneManager::get_milestone(&e, milestone_index)
+    }
+
+    pub fn get_milestones(e: Env, offset: u32, limit: u32) -> Vec<Milestone> {
+        MilestoneManager::get_milestones(&e, offset, limit)
+    }
+
+    pub fn get_milestone_count(e: Env) -> u32 {
+        MilestoneManager::get_milestone_count(&e)
+    }
+
+    pub fn add_milestone(
+        e: Env,
+        platform_address: Address,
+        milestone: Milestone,
+    ) -> Result<(), ContractError> {
+        let (milestone_index, escrow) =
+            MilestoneManager::add_milestone(&e, platform_address.clone(), milestone.clone())?;
+        MilestoneAdded {
+            engagement_id: escrow.engagement_id,
+            platform: platform_address,
+            milestone_index,
+            milestone,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    /// Adds several milestones at once, each announced by its own `MilestoneAdded` event.
+    pub fn add_milestones(
+        e: Env,
+        platform_address: Address,
+        milestones: Vec<Milestone>,
+    ) -> Result<(), ContractError> {
+        let (first_index, escrow) =
+            MilestoneManager::add_milestones(&e, platform_address.clone(), milestones.clone())?;
+        for (offset, milestone) in milestones.iter().enumerate() {
+            MilestoneAdded {
+                engagement_id: escrow.engagement_id.clone(),
+                platform: platform_address.clone(),
+                milestone_index: first_index + offset as u32,
+                milestone,
+            }
+            .publish(&e);
+        }
+        Ok(())
+    }
+
+    pub fn remove_milestone(
+        e: Env,
+        platform_address: Address,
+        milestone_index: i128,
+    ) -> Result<(), ContractError> {
+        let (milestone, moved_from_index, escrow) =
+            MilestoneManager::remove_milestone(&e, platform_address.clone(), milestone_index)?;
+        MilestoneRemoved {
+            engagement_id: escrow.engagement_id,
+            platform: platform_address,
+            milestone_index: milestone_index as u32,
+            milestone,
+            moved_from_index,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    pub fn edit_milestone_description(
+        e: Env,
+        platform_address: Address,
+        milestone_index: i128,
+        new_description: String,
+    ) -> Result<(), ContractError> {
+        let (old_description, escrow) = MilestoneManager::edit_milestone_description(
+            &e,
+            platform_address.clone(),
+            milestone_index,
+            new_description.clone(),
+        )?;
+        MilestoneDescriptionChanged {
+            engagement_id: escrow.engagement_id,
+            platform: platform_address,
+            milestone_index: milestone_index as u32,
+            old_description,
+            new_description,
+        }
+        .publish(&e);
         Ok(())
     }
 
@@ -178,21 +713,91 @@ impl EscrowContract {
         e: Env,
         dispute_resolver: Address,
         trustless_work_address: Address,
-        distributions: Map<Address, i128>,
+        distributions: Map<Address, Map<Address, i128>>,
     ) -> Result<(), ContractError> {
-        let escrow = DisputeManager::resolve_dispute(
+        let (escrow, breakdowns) = DisputeManager::resolve_dispute(
             &e,
-            dispute_resolver,
-            trustless_work_address,
+            dispute_resolver.clone(),
+            trustless_work_address.clone(),
             distributions,
         )?;
-        DisputeResolved { escrow }.publish(&e);
+        for breakdown in breakdowns.iter() {
+            DisputePayout {
+                engagement_id: escrow.engagement_id.clone(),
+                dispute_resolver: dispute_resolver.clone(),
+                token: breakdown.token,
+                trustless_work_address: trustless_work_address.clone(),
+                trustless_work_fee: breakdown.trustless_work_fee,
+                platform_address: escrow.roles.platform_address.clone(),
+                platform_fee: breakdown.platform_fee,
+                payouts: breakdown.payouts,
+            }
+            .publish(&e);
+        }
+        DisputeResolved {
+            engagement_id: escrow.engagement_id,
+            dispute_resolver,
+        }
+        .publish(&e);
         Ok(())
     }
 
+    pub fn preview_dispute_resolution(
+        e: &Env,
+        distributions: Map<Address, Map<Address, i128>>,
+    ) -> Result<Vec<PayoutBreakdown>, ContractError> {
+        DisputeManager::preview_dispute_resolution(e, distributions)
+    }
+
     pub fn dispute_escrow(e: Env, signer: Address) -> Result<(), ContractError> {
-        let escrow = DisputeManager::dispute_escrow(&e, signer)?;
-        EscrowDisputed { escrow }.publish(&e);
+        let escrow = DisputeManager::dispute_escrow(&e, signer.clone())?;
+        EscrowDisputed {
+            engagement_id: escrow.engagement_id,
+            signer,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    ////////////////////////
+    // Roles /////
+    ////////////////////////
+
+    pub fn propose_role_change(
+        e: Env,
+        proposers: Vec<Address>,
+        role: Role,
+        new_address: Address,
+    ) -> Result<(), ContractError> {
+        let proposal = RoleManager::propose_role_change(&e, &proposers, role, &new_address)?;
+        RoleChangeProposed {
+            engagement_id: EscrowManager::get_engagement_id(&e),
+            role,
+            new_address,
+            proposers,
+            expiration_ledger: proposal.expiration_ledger,
+        }
+        .publish(&e);
         Ok(())
     }
+
+    pub fn accept_role_change(
+        e: Env,
+        role: Role,
+        new_address: Address,
+    ) -> Result<(), ContractError> {
+        let (escrow, previous_address) = RoleManager::accept_role_change(&e, role, &new_address)?;
+        RoleChanged {
+            engagement_id: escrow.engagement_id,
+            role,
+            previous_address,
+            new_address,
+        }
+        .publish(&e);
+        Ok(())
+    }
+
+    pub fn get_pending_role_change(e: Env, role: Role) -> Option<RoleChangeProposal> {
+        RoleManager::get_pending_role_change(&e, role)
+    }
 }
@@ -0,0 +1,373 @@
+use soroban_sdk::{vec, Address, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec};
+
+use crate::core::validators::admin::{
+    validate_admin, validate_max_batch_size, validate_max_platform_fee,
+    validate_min_receiver_share, validate_not_paused, validate_proposal_acceptance,
+    validate_protocol_fee,
+};
+use crate::error::ContractError;
+use crate::modules::fee::{
+    FeeLimits, DEFAULT_MIN_RECEIVER_SHARE_BPS, MAX_PLATFORM_FEE_BPS, TRUSTLESS_WORK_FEE_BPS,
+};
+use crate::storage::store::{load_instance, remove_instance, save_instance};
+use crate::storage::types::{DataKey, Governance};
+
+/// Batch size used by the multi-escrow queries until the admin configures one.
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 20;
+/// Upper bound for the configurable batch size; each entry costs a cross-contract call.
+pub const MAX_BATCH_SIZE_LIMIT: u32 = 50;
+
+/// Protocol settings live on a single governance contract, the root instance deployments
+/// are made from. Instances governed by it read every setting from it and reject setters;
+/// only the escrow pause stays per instance.
+pub struct AdminManager;
+
+impl AdminManager {
+    pub fn set_governance(e: &Env, governance: &Governance) {
+        match governance {
+            Governance::Admin(admin) => Self::set_admin(e, admin),
+            Governance::Contract(contract) => save_instance(e, &DataKey::Governance, contract),
+        }
+    }
+
+    /// The contract this instance reads its protocol settings from, `None` when it governs
+    /// itself.
+    pub fn get_governance(e: &Env) -> Option<Address> {
+        load_instance(e, &DataKey::Governance)
+    }
+
+    /// The governance contract deployments from this instance should answer to.
+    pub fn get_governance_root(e: &Env) -> Address {
+        Self::get_governance(e).unwrap_or_else(|| e.current_contract_address())
+    }
+
+    fn require_governance_contract(e: &Env) -> Result<(), ContractError> {
+        if Self::get_governance(e).is_some() {
+            return Err(ContractError::NotGovernanceContract);
+        }
+
+        Ok(())
+    }
+
+    /// Reads a setting from the governance contract, or from this instance when it governs
+    /// itself.
+    fn read_setting<V: TryFromVal<Env, Val>>(
+        e: &Env,
+        getter: &str,
+        args: Vec<Val>,
+        read_local: impl FnOnce() -> V,
+    ) -> V {
+        match Self::get_governance(e) {
+            Some(governance) => e.invoke_contract(&governance, &Symbol::new(e, getter), args),
+            None => read_local(),
+        }
+    }
+
+    /// Checks `admin` is the protocol admin and that settings are kept on this instance.
+    fn require_protocol_admin(e: &Env, admin: &Address) -> Result<(), ContractError> {
+        Self::require_governance_contract(e)?;
+        Self::require_admin(e, admin)
+    }
+
+    pub fn set_admin(e: &Env, admin: &Address) {
+        save_instance(e, &DataKey::Admin, admin);
+    }
+
+    pub fn get_admin(e: &Env) -> Result<Address, ContractError> {
+        match Self::get_governance(e) {
+            Some(governance) => match e.try_invoke_contract::<Address, ContractError>(
+                &governance,
+                &Symbol::new(e, "get_admin"),
+                Vec::new(e),
+            ) {
+                Ok(Ok(admin)) => Ok(admin),
+                _ => Err(ContractError::AdminNotFound),
+            },
+            None => load_instance(e, &DataKey::Admin).ok_or(ContractError::AdminNotFound),
+        }
+    }
+
+    pub fn require_admin(e: &Env, signer: &Address) -> Result<(), ContractError> {
+        signer.require_auth();
+        let admin = Self::get_admin(e)?;
+        validate_admin(&admin, signer)
+    }
+
+    pub fn propose_admin(
+        e: &Env,
+        admin: &Address,
+        new_admin: &Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        save_instance(e, &DataKey::PendingAdmin, new_admin);
+        Ok(())
+    }
+
+    pub fn accept_admin(e: &Env, new_admin: &Address) -> Result<Address, ContractError> {
+        new_admin.require_auth();
+        Self::require_governance_contract(e)?;
+        let previous_admin = Self::get_admin(e)?;
+        validate_proposal_acceptance(Self::get_pending_admin(e), new_admin)?;
+
+        Self::set_admin(e, new_admin);
+        remove_instance(e, &DataKey::PendingAdmin);
+        Ok(previous_admin)
+    }
+
+    pub fn get_pending_admin(e: &Env) -> Option<Address> {
+        Self::read_setting(e, "get_pending_admin", Vec::new(e), || {
+            load_instance(e, &DataKey::PendingAdmin)
+        })
+    }
+
+    pub fn set_protocol_fee(e: &Env, admin: &Address, fee_bps: u32) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        validate_protocol_fee(fee_bps)?;
+        save_instance(e, &DataKey::ProtocolFeeBps, &fee_bps);
+        Ok(())
+    }
+
+    pub fn get_protocol_fee(e: &Env) -> u32 {
+        Self::read_setting(e, "get_protocol_fee", Vec::new(e), || {
+            load_instance(e, &DataKey::ProtocolFeeBps).unwrap_or(TRUSTLESS_WORK_FEE_BPS)
+        })
+    }
+
+    /// Sets a partner platform's discounted protocol fee, replacing any previous discount.
+    pub fn set_platform_fee_discount(
+        e: &Env,
+        admin: &Address,
+        platform: &Address,
+        fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        validate_protocol_fee(fee_bps)?;
+        let mut discounts = Self::get_platform_fee_discounts(e);
+        discounts.set(platform.clone(), fee_bps);
+        save_instance(e, &DataKey::PlatformFeeDiscounts, &discounts);
+        Ok(())
+    }
+
+    pub fn remove_platform_fee_discount(
+        e: &Env,
+        admin: &Address,
+        platform: &Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        let mut discounts = Self::get_platform_fee_discounts(e);
+        discounts.remove(platform.clone());
+        save_instance(e, &DataKey::PlatformFeeDiscounts, &discounts);
+        Ok(())
+    }
+
+    pub fn get_platform_fee_discounts(e: &Env) -> Map<Address, u32> {
+        Self::read_setting(e, "get_platform_fee_discounts", Vec::new(e), || {
+            load_instance(e, &DataKey::PlatformFeeDiscounts).unwrap_or(Map::new(e))
+        })
+    }
+
+    /// Protocol fee charged on a platform's escrows. A discount never raises the fee above
+    /// the current protocol fee.
+    pub fn get_platform_protocol_fee(e: &Env, platform: &Address) -> u32 {
+        Self::read_setting(
+            e,
+            "get_platform_protocol_fee",
+            vec![e, platform.into_val(e)],
+            || {
+                let protocol_fee = Self::get_protocol_fee(e);
+                match Self::get_platform_fee_discounts(e).get(platform.clone()) {
+                    Some(discounted_fee) => discounted_fee.min(protocol_fee),
+                    None => protocol_fee,
+                }
+            },
+        )
+    }
+
+    pub fn set_fee_collector(
+        e: &Env,
+        admin: &Address,
+        fee_collector: &Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        save_instance(e, &DataKey::FeeCollector, fee_collector);
+        Ok(())
+    }
+
+    pub fn get_fee_collector(e: &Env) -> Option<Address> {
+        Self::read_setting(e, "get_fee_collector", Vec::new(e), || {
+            load_instance(e, &DataKey::FeeCollector)
+        })
+    }
+
+    pub fn set_wasm_hash_allowed(
+        e: &Env,
+        admin: &Address,
+        wasm_hash: &BytesN<32>,
+        allowed: bool,
+    ) -> Result<Vec<BytesN<32>>, ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        let mut allowlist = Self::get_wasm_allowlist(e);
+        let position = allowlist.first_index_of(wasm_hash);
+
+        match (allowed, position) {
+            (true, None) => allowlist.push_back(wasm_hash.clone()),
+            (false, Some(index)) => {
+                allowlist.remove(index);
+            }
+            _ => {}
+        }
+
+        save_instance(e, &DataKey::WasmAllowlist, &allowlist);
+        Ok(allowlist)
+    }
+
+    pub fn get_wasm_allowlist(e: &Env) -> Vec<BytesN<32>> {
+        Self::read_setting(e, "get_wasm_allowlist", Vec::new(e), || {
+            load_instance(e, &DataKey::WasmAllowlist).unwrap_or(Vec::new(e))
+        })
+    }
+
+    pub fn is_wasm_hash_allowed(e: &Env, wasm_hash: &BytesN<32>) -> bool {
+        Self::get_wasm_allowlist(e).contains(wasm_hash)
+    }
+
+    /// Adds or removes a token from a platform's allowlist. The first token added opts the
+    /// platform in, and it stays restricted, even to no token at all, until the admin
+    /// clears its list.
+    pub fn set_token_allowed(
+        e: &Env,
+        admin: &Address,
+        platform: &Address,
+        token: &Address,
+        allowed: bool,
+    ) -> Result<Vec<Address>, ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        let mut allowlist = Self::get_token_allowlist(e, platform).unwrap_or(Vec::new(e));
+        let position = allowlist.first_index_of(token);
+
+        match (allowed, position) {
+            (true, None) => allowlist.push_back(token.clone()),
+            (false, Some(index)) => {
+                allowlist.remove(index);
+            }
+            _ => {}
+        }
+
+        save_instance(e, &DataKey::TokenAllowlist(platform.clone()), &allowlist);
+        Ok(allowlist)
+    }
+
+    /// Opts a platform out of the allowlist, letting its escrows use any token again.
+    pub fn clear_token_allowlist(
+        e: &Env,
+        admin: &Address,
+        platform: &Address,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        remove_instance(e, &DataKey::TokenAllowlist(platform.clone()));
+        Ok(())
+    }
+
+    /// Tokens the platform's escrows are limited to, `None` when the platform has not opted
+    /// into an allowlist.
+    pub fn get_token_allowlist(e: &Env, platform: &Address) -> Option<Vec<Address>> {
+        Self::read_setting(
+            e,
+            "get_token_allowlist",
+            vec![e, platform.into_val(e)],
+            || load_instance(e, &DataKey::TokenAllowlist(platform.clone())),
+        )
+    }
+
+    pub fn set_protocol_paused(
+        e: &Env,
+        admin: &Address,
+        paused: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        save_instance(e, &DataKey::ProtocolPaused, &paused);
+        Ok(())
+    }
+
+    /// Pauses every escrow governed by this contract at once.
+    pub fn is_protocol_paused(e: &Env) -> bool {
+        Self::read_setting(e, "is_protocol_paused", Vec::new(e), || {
+            load_instance(e, &DataKey::ProtocolPaused).unwrap_or(false)
+        })
+    }
+
+    pub fn set_escrow_paused(e: &Env, admin: &Address, paused: bool) -> Result<(), ContractError> {
+        Self::require_admin(e, admin)?;
+        save_instance(e, &DataKey::EscrowPaused, &paused);
+        Ok(())
+    }
+
+    pub fn is_escrow_paused(e: &Env) -> bool {
+        load_instance(e, &DataKey::EscrowPaused).unwrap_or(false)
+    }
+
+    pub fn set_max_batch_size(
+        e: &Env,
+        admin: &Address,
+        max_batch_size: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        validate_max_batch_size(max_batch_size)?;
+        save_instance(e, &DataKey::MaxBatchSize, &max_batch_size);
+        Ok(())
+    }
+
+    pub fn get_max_batch_size(e: &Env) -> u32 {
+        Self::read_setting(e, "get_max_batch_size", Vec::new(e), || {
+            load_instance(e, &DataKey::MaxBatchSize).unwrap_or(DEFAULT_MAX_BATCH_SIZE)
+        })
+    }
+
+    pub fn set_max_platform_fee_bps(
+        e: &Env,
+        admin: &Address,
+        max_platform_fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        validate_max_platform_fee(max_platform_fee_bps)?;
+        save_instance(e, &DataKey::MaxPlatformFeeBps, &max_platform_fee_bps);
+        Ok(())
+    }
+
+    /// Protocol-level cap on the platform fee of any release, whatever the fee schedule.
+    pub fn get_max_platform_fee_bps(e: &Env) -> u32 {
+        Self::read_setting(e, "get_max_platform_fee_bps", Vec::new(e), || {
+            load_instance(e, &DataKey::MaxPlatformFeeBps).unwrap_or(MAX_PLATFORM_FEE_BPS)
+        })
+    }
+
+    pub fn set_min_receiver_share_bps(
+        e: &Env,
+        admin: &Address,
+        min_receiver_share_bps: u32,
+    ) -> Result<(), ContractError> {
+        Self::require_protocol_admin(e, admin)?;
+        validate_min_receiver_share(min_receiver_share_bps)?;
+        save_instance(e, &DataKey::MinReceiverShareBps, &min_receiver_share_bps);
+        Ok(())
+    }
+
+    pub fn get_min_receiver_share_bps(e: &Env) -> u32 {
+        Self::read_setting(e, "get_min_receiver_share_bps", Vec::new(e), || {
+            load_instance(e, &DataKey::MinReceiverShareBps)
+                .unwrap_or(DEFAULT_MIN_RECEIVER_SHARE_BPS)
+        })
+    }
+
+    pub fn get_fee_limits(e: &Env, platform: &Address) -> FeeLimits {
+        FeeLimits {
+            protocol_fee_bps: Self::get_platform_protocol_fee(e, platform),
+            max_platform_fee_bps: Self::get_max_platform_fee_bps(e),
+            min_receiver_share_bps: Self::get_min_receiver_share_bps(e),
+        }
+    }
+
+    pub fn require_not_paused(e: &Env) -> Result<(), ContractError> {
+        validate_not_paused(Self::is_protocol_paused(e), Self::is_escrow_paused(e))
+    }
+}
@@ -1,14 +1,18 @@
 use soroban_sdk::token::Client as TokenClient;
-use soroban_sdk::{Address, Env, Map};
+use soroban_sdk::{Address, Env, Map, Vec};
 
+use crate::core::admin::AdminManager;
 use crate::core::escrow::EscrowManager;
+use crate::core::history::HistoryManager;
 use crate::error::ContractError;
 use crate::modules::{
     fee::{FeeCalculator, FeeCalculatorTrait},
     math::{BasicArithmetic, BasicMath},
 };
-use crate::storage::types::{DataKey, Escrow};
+use crate::storage::store::save_escrow;
+use crate::storage::types::{AuditAction, Escrow, Payout, PayoutBreakdown};
 
+use super::validators::admin::validate_trustless_work_address;
 use super::validators::dispute::{
     validate_dispute_flag_change_conditions, validate_dispute_resolution_conditions,
 };
@@ -20,15 +24,123 @@ impl DisputeManager {
         e: &Env,
         dispute_resolver: Address,
         trustless_work_address: Address,
-        distributions: Map<Address, i128>,
-    ) -> Result<Escrow, ContractError> {
+        distributions: Map<Address, Map<Address, i128>>,
+    ) -> Result<(Escrow, Vec<PayoutBreakdown>), ContractError> {
         dispute_resolver.require_auth();
         let mut escrow = EscrowManager::get_escrow(e)?;
+        let breakdowns = Self::plan_resolution(e, &escrow, &dispute_resolver, &distributions)?;
+        validate_trustless_work_address(
+            &AdminManager::get_fee_collector(e),
+            &trustless_work_address,
+        )?;
+
         let contract_address = e.current_contract_address();
+        for breakdown in breakdowns.iter() {
+            let token_client = TokenClient::new(e, &breakdown.token);
+
+            if breakdown.trustless_work_fee > 0 {
+                token_client.transfer(
+                    &contract_address,
+                    &trustless_work_address,
+                    &breakdown.trustless_work_fee,
+                );
+            }
+            if breakdown.platform_fee > 0 {
+                token_client.transfer(
+                    &contract_address,
+                    &escrow.roles.platform_address,
+                    &breakdown.platform_fee,
+                );
+            }
+            for payout in breakdown.payouts.iter() {
+                token_client.transfer(&contract_address, &payout.recipient, &payout.amount);
+            }
+        }
+
+        escrow.flags.resolved = true;
+        escrow.flags.disputed = false;
+        save_escrow(e, &escrow);
+
+        for (token, token_distributions) in distributions.iter() {
+            let total: i128 = token_distributions.values().iter().sum();
+            HistoryManager::record_leg(
+                e,
+                AuditAction::DisputeResolved,
+                &dispute_resolver,
+                &token,
+                total,
+            );
+        }
+
+        Ok((escrow, breakdowns))
+    }
+
+    /// Dry run of `resolve_dispute` as signed by the configured dispute resolver.
+    pub fn preview_dispute_resolution(
+        e: &Env,
+        distributions: Map<Address, Map<Address, i128>>,
+    ) -> Result<Vec<PayoutBreakdown>, ContractError> {
+        let escrow = EscrowManager::get_escrow(e)?;
+        Self::plan_resolution(e, &escrow, &escrow.roles.dispute_resolver, &distributions)
+    }
+
+    /// Runs every check `resolve_dispute` makes before moving tokens. `distributions` maps
+    /// each token to its recipients; every leg holding a balance must be distributed in
+    /// full, and legs that are empty and undistributed are skipped.
+    fn plan_resolution(
+        e: &Env,
+        escrow: &Escrow,
+        dispute_resolver: &Address,
+        distributions: &Map<Address, Map<Address, i128>>,
+    ) -> Result<Vec<PayoutBreakdown>, ContractError> {
+        AdminManager::require_not_paused(e)?;
+
+        let legs = EscrowManager::get_token_legs(e, escrow);
+        for token in distributions.keys().iter() {
+            if !legs.iter().any(|leg| leg.trustline.address == token) {
+                return Err(ContractError::TokenNotInEscrow);
+            }
+        }
 
-        let token_client = TokenClient::new(&e, &escrow.trustline.address);
-        let current_balance = token_client.balance(&contract_address);
+        let mut breakdowns: Vec<PayoutBreakdown> = Vec::new(e);
+        for leg in legs.iter() {
+            let token = leg.trustline.address;
+            let token_client = TokenClient::new(e, &token);
+            let current_balance = token_client.balance(&e.current_contract_address());
+            let token_distributions = distributions.get(token.clone()).unwrap_or(Map::new(e));
+            if current_balance == 0 && token_distributions.is_empty() {
+                continue;
+            }
+
+            breakdowns.push_back(Self::plan_token_resolution(
+                e,
+                escrow,
+                dispute_resolver,
+                token,
+                current_balance,
+                &token_distributions,
+            )?);
+        }
+
+        if breakdowns.is_empty() {
+            validate_dispute_resolution_conditions(escrow, dispute_resolver, 0, 0)?;
+        }
 
+        Ok(breakdowns)
+    }
+
+    /// Splits the fees of one token across its recipients pro rata. Recipients whose net
+    /// share rounds to zero are left out. The escrow's `fee_mode` is not applied: the held
+    /// balance already includes any surcharge the funders deposited, so the standard fees
+    /// are charged on the distributed total and the resolver decides who bears them.
+    fn plan_token_resolution(
+        e: &Env,
+        escrow: &Escrow,
+        dispute_resolver: &Address,
+        token: Address,
+        current_balance: i128,
+        distributions: &Map<Address, i128>,
+    ) -> Result<PayoutBreakdown, ContractError> {
         let mut total: i128 = 0;
         for (_addr, amount) in distributions.iter() {
             if amount <= 0 {
@@ -38,47 +150,38 @@ impl DisputeManager {
         }
 
         validate_dispute_resolution_conditions(
-            &escrow,
-            &dispute_resolver,
+            escrow,
+            dispute_resolver,
             current_balance,
             total,
         )?;
 
-        let fee_result = FeeCalculator::calculate_standard_fees(total, escrow.platform_fee)?;
+        let fee_result = FeeCalculator::for_escrow(escrow, &token).calculate_standard_fees(
+            total,
+            AdminManager::get_platform_protocol_fee(e, &escrow.roles.platform_address),
+            AdminManager::get_max_platform_fee_bps(e),
+        )?;
         let total_fees =
             BasicMath::safe_add(fee_result.trustless_work_fee, fee_result.platform_fee)?;
 
-        if fee_result.trustless_work_fee > 0 {
-            token_client.transfer(
-                &contract_address,
-                &trustless_work_address,
-                &fee_result.trustless_work_fee,
-            );
-        }
-        if fee_result.platform_fee > 0 {
-            token_client.transfer(
-                &contract_address,
-                &escrow.roles.platform_address,
-                &fee_result.platform_fee,
-            );
-        }
-
+        let mut payouts: Vec<Payout> = Vec::new(e);
         for (addr, amount) in distributions.iter() {
-            if amount <= 0 {
-                continue;
-            }
-            let fee_share = (amount * (total_fees as i128)) / total;
+            let fee_share = (amount * total_fees) / total;
             let net_amount = amount - fee_share;
             if net_amount > 0 {
-                token_client.transfer(&contract_address, &addr, &net_amount);
+                payouts.push_back(Payout {
+                    recipient: addr,
+                    amount: net_amount,
+                });
             }
         }
 
-        escrow.flags.resolved = true;
-        escrow.flags.disputed = false;
-        e.storage().instance().set(&DataKey::Escrow, &escrow);
-
-        Ok(escrow)
+        Ok(PayoutBreakdown {
+            token,
+            trustless_work_fee: fee_result.trustless_work_fee,
+            platform_fee: fee_result.platform_fee,
+            payouts,
+        })
     }
 
     pub fn dispute_escrow(e: &Env, signer: Address) -> Result<Escrow, ContractError> {
@@ -87,7 +190,8 @@ impl DisputeManager {
         validate_dispute_flag_change_conditions(&escrow, &signer)?;
 
         escrow.flags.disputed = true;
-        e.storage().instance().set(&DataKey::Escrow, &escrow);
+        save_escrow(e, &escrow);
+        HistoryManager::record(e, AuditAction::Disputed, &signer);
 
         Ok(escrow)
     }
@@ -1,13 +1,29 @@
 use soroban_sdk::token::Client as TokenClient;
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
+use crate::core::admin::AdminManager;
+use crate::core::history::HistoryManager;
+use crate::core::milestone::MilestoneManager;
+use crate::core::validators::admin::validate_trustless_work_address;
 use crate::core::validators::escrow::{
-    validate_escrow_property_change_conditions, validate_fund_escrow_conditions,
-    validate_initialize_escrow_conditions, validate_release_conditions,
+    validate_amount_change_conditions, validate_batch_size,
+    validate_escrow_property_change_conditions, validate_fees, validate_fund_escrow_conditions,
+    validate_initialize_escrow_conditions, validate_leg_amount, validate_partial_update_conditions,
+    validate_release_conditions, validate_trustline, validate_update_approval,
 };
 use crate::error::ContractError;
 use crate::modules::fee::{FeeCalculator, FeeCalculatorTrait};
-use crate::storage::types::{AddressBalance, DataKey, Escrow};
+use crate::modules::fee::{ReleaseFeeResult, BASIS_POINTS_DENOMINATOR};
+use crate::modules::math::{BasicArithmetic, BasicMath, SafeArithmetic, SafeMath};
+use crate::storage::store::{load_escrow, load_pending_update, save_escrow, save_pending_update};
+use crate::storage::ttl::DAY_IN_LEDGERS;
+use crate::storage::types::{
+    AddressBalance, AuditAction, Escrow, EscrowStatus, EscrowSummary, EscrowSummaryEntry,
+    LegFunding, Milestone, Payout, PayoutBreakdown, TokenLeg, Trustline, UpdateProposal,
+};
+
+/// Ledgers a pending `update_escrow` proposal stays open for.
+pub const UPDATE_PROPOSAL_TTL_LEDGERS: u32 = DAY_IN_LEDGERS;
 
 pub struct EscrowManager;
 
@@ -17,11 +33,30 @@ impl EscrowManager {
         escrow.roles.receiver.clone()
     }
 
-    pub fn initialize_escrow(e: &Env, escrow_properties: Escrow) -> Result<Escrow, ContractError> {
-        validate_initialize_escrow_conditions(e, escrow_properties.clone())?;
-        e.storage()
-            .instance()
-            .set(&DataKey::Escrow, &escrow_properties);
+    pub fn initialize_escrow(
+        e: &Env,
+        mut escrow_properties: Escrow,
+        milestones: Vec<Milestone>,
+    ) -> Result<Escrow, ContractError> {
+        Self::resolve_trustlines(e, &mut escrow_properties)?;
+        validate_initialize_escrow_conditions(
+            e,
+            escrow_properties.clone(),
+            &milestones,
+            &AdminManager::get_fee_limits(e, &escrow_properties.roles.platform_address),
+            &AdminManager::get_token_allowlist(e, &escrow_properties.roles.platform_address),
+        )?;
+        save_escrow(e, &escrow_properties);
+        MilestoneManager::store_initial_milestones(e, &milestones);
+        for leg in Self::get_token_legs(e, &escrow_properties).iter() {
+            HistoryManager::record_leg(
+                e,
+                AuditAction::Initialized,
+                &escrow_properties.roles.platform_address,
+                &leg.trustline.address,
+                leg.amount,
+            );
+        }
         Ok(escrow_properties)
     }
 
@@ -29,14 +64,17 @@ impl EscrowManager {
         e: &Env,
         signer: &Address,
         expected_escrow: &Escrow,
+        token: &Address,
         amount: i128,
     ) -> Result<(), ContractError> {
         let stored_escrow: Escrow = Self::get_escrow(e)?;
-        validate_fund_escrow_conditions(amount, &stored_escrow, expected_escrow)?;
+        AdminManager::require_not_paused(e)?;
+        validate_fund_escrow_conditions(token, amount, &stored_escrow, expected_escrow)?;
 
         signer.require_auth();
-        let token_client = TokenClient::new(e, &stored_escrow.trustline.address);
-        token_client.transfer(signer, &e.current_contract_address(), &amount);
+        let token_client = TokenClient::new(e, token);
+        token_client.transfer(signer, e.current_contract_address(), &amount);
+        HistoryManager::record_leg(e, AuditAction::Funded, signer, token, amount);
         Ok(())
     }
 
@@ -44,103 +82,668 @@ impl EscrowManager {
         e: &Env,
         release_signer: &Address,
         trustless_work_address: &Address,
-    ) -> Result<(), ContractError> {
+    ) -> Result<(Escrow, Vec<PayoutBreakdown>), ContractError> {
         release_signer.require_auth();
 
         let mut escrow = Self::get_escrow(e)?;
-        validate_release_conditions(&escrow, release_signer)?;
+        let breakdowns = Self::plan_release(e, &escrow, release_signer)?;
+        validate_trustless_work_address(
+            &AdminManager::get_fee_collector(e),
+            trustless_work_address,
+        )?;
 
         escrow.flags.released = true;
-        e.storage().instance().set(&DataKey::Escrow, &escrow);
+        save_escrow(e, &escrow);
 
         let contract_address = e.current_contract_address();
-        let token_client = TokenClient::new(e, &escrow.trustline.address);
+        for breakdown in breakdowns.iter() {
+            let token_client = TokenClient::new(e, &breakdown.token);
 
-        if token_client.balance(&contract_address) < escrow.amount {
-            return Err(ContractError::EscrowBalanceNotEnoughToSendEarnings);
+            token_client.transfer(
+                &contract_address,
+                trustless_work_address,
+                &breakdown.trustless_work_fee,
+            );
+            token_client.transfer(
+                &contract_address,
+                &escrow.roles.platform_address,
+                &breakdown.platform_fee,
+            );
+            let mut released =
+                BasicMath::safe_add(breakdown.trustless_work_fee, breakdown.platform_fee)?;
+            for payout in breakdown.payouts.iter() {
+                token_client.transfer(&contract_address, &payout.recipient, &payout.amount);
+                released = BasicMath::safe_add(released, payout.amount)?;
+            }
+            HistoryManager::record_leg(
+                e,
+                AuditAction::Released,
+                release_signer,
+                &breakdown.token,
+                released,
+            );
         }
 
-        let fee_result =
-            FeeCalculator::calculate_standard_fees(escrow.amount as i128, escrow.platform_fee)?;
+        Ok((escrow, breakdowns))
+    }
 
-        token_client.transfer(
-            &contract_address,
-            trustless_work_address,
-            &fee_result.trustless_work_fee,
-        );
-        token_client.transfer(
-            &contract_address,
-            &escrow.roles.platform_address,
-            &fee_result.platform_fee,
-        );
+    /// Dry run of `release_funds` as signed by the configured release signer. The fee
+    /// collector check is skipped since no Trustless Work address is supplied.
+    pub fn preview_release(e: &Env) -> Result<Vec<PayoutBreakdown>, ContractError> {
+        let escrow = Self::get_escrow(e)?;
+        Self::plan_release(e, &escrow, &escrow.roles.release_signer)
+    }
+
+    /// Runs every check `release_funds` makes before moving tokens and returns the payouts,
+    /// one breakdown per leg.
+    fn plan_release(
+        e: &Env,
+        escrow: &Escrow,
+        release_signer: &Address,
+    ) -> Result<Vec<PayoutBreakdown>, ContractError> {
+        AdminManager::require_not_paused(e)?;
+        validate_release_conditions(
+            escrow,
+            release_signer,
+            MilestoneManager::get_milestone_count(e),
+            MilestoneManager::get_approved_milestone_count(e),
+        )?;
 
-        let receiver = Self::get_receiver(&escrow);
-        token_client.transfer(&contract_address, &receiver, &fee_result.receiver_amount);
+        let mut breakdowns: Vec<PayoutBreakdown> = Vec::new(e);
+        for leg in Self::get_token_legs(e, escrow).iter() {
+            let fee_result =
+                Self::calculate_release_fees(e, escrow, &leg.trustline.address, leg.amount)?;
+            let required_funding = BasicMath::safe_add(leg.amount, fee_result.funder_surcharge)?;
+            let token_client = TokenClient::new(e, &leg.trustline.address);
+            if token_client.balance(&e.current_contract_address()) < required_funding {
+                return Err(ContractError::EscrowBalanceNotEnoughToSendEarnings);
+            }
 
-        Ok(())
+            breakdowns.push_back(PayoutBreakdown {
+                token: leg.trustline.address,
+                trustless_work_fee: fee_result.trustless_work_fee,
+                platform_fee: fee_result.platform_fee,
+                payouts: Self::split_receiver_amount(e, escrow, fee_result.receiver_amount)?,
+            });
+        }
+
+        Ok(breakdowns)
+    }
+
+    /// Every (token, amount) leg of the escrow, the `trustline` and `amount` pair first.
+    pub fn get_token_legs(e: &Env, escrow: &Escrow) -> Vec<TokenLeg> {
+        let mut legs: Vec<TokenLeg> = Vec::new(e);
+        legs.push_back(TokenLeg {
+            trustline: escrow.trustline.clone(),
+            amount: escrow.amount,
+        });
+        legs.append(&escrow.extra_legs);
+        legs
+    }
+
+    #[inline]
+    fn calculate_release_fees(
+        e: &Env,
+        escrow: &Escrow,
+        token: &Address,
+        amount: i128,
+    ) -> Result<ReleaseFeeResult, ContractError> {
+        FeeCalculator::for_escrow(escrow, token).calculate_release_fees(
+            amount,
+            AdminManager::get_platform_protocol_fee(e, &escrow.roles.platform_address),
+            AdminManager::get_max_platform_fee_bps(e),
+            escrow.fee_mode,
+        )
+    }
+
+    /// What funders must deposit per leg before the escrow can be released: the amount
+    /// plus any fees the fee mode charges on top, next to what the escrow holds.
+    pub fn get_required_funding(e: &Env) -> Result<Vec<LegFunding>, ContractError> {
+        let escrow = Self::get_escrow(e)?;
+        Self::leg_funding(e, &escrow)
+    }
+
+    fn leg_funding(e: &Env, escrow: &Escrow) -> Result<Vec<LegFunding>, ContractError> {
+        let contract_address = e.current_contract_address();
+        let mut funding: Vec<LegFunding> = Vec::new(e);
+        for leg in Self::get_token_legs(e, escrow).iter() {
+            let fee_result =
+                Self::calculate_release_fees(e, escrow, &leg.trustline.address, leg.amount)?;
+            let token_client = TokenClient::new(e, &leg.trustline.address);
+            let total_funded = match token_client.try_balance(&contract_address) {
+                Ok(Ok(balance)) => balance,
+                _ => return Err(ContractError::InvalidTrustlineToken),
+            };
+
+            funding.push_back(LegFunding {
+                token: leg.trustline.address,
+                amount: leg.amount,
+                required_funding: BasicMath::safe_add(leg.amount, fee_result.funder_surcharge)?,
+                total_funded,
+            });
+        }
+        Ok(funding)
+    }
+
+    /// Splits the receiver's amount by each payee's share. Shares are rounded down and the
+    /// leftover dust goes to the first payee, so the payouts always add up to `amount`.
+    fn split_receiver_amount(
+        e: &Env,
+        escrow: &Escrow,
+        amount: i128,
+    ) -> Result<Vec<Payout>, ContractError> {
+        let mut payouts: Vec<Payout> = Vec::new(e);
+        if escrow.payees.is_empty() {
+            payouts.push_back(Payout {
+                recipient: Self::get_receiver(escrow),
+                amount,
+            });
+            return Ok(payouts);
+        }
+
+        let mut distributed: i128 = 0;
+        for payee in escrow.payees.iter() {
+            let share = SafeMath::safe_mul_div(amount, payee.bps, BASIS_POINTS_DENOMINATOR)?;
+            distributed = BasicMath::safe_add(distributed, share)?;
+            payouts.push_back(Payout {
+                recipient: payee.address,
+                amount: share,
+            });
+        }
+
+        let dust = BasicMath::safe_sub(amount, distributed)?;
+        let mut first = payouts.get_unchecked(0);
+        first.amount = BasicMath::safe_add(first.amount, dust)?;
+        payouts.set(0, first);
+
+        Ok(payouts)
     }
+    /// Proposes new escrow properties. Changes that only touch the title, description or
+    /// engagement id are applied right away; changes to economic terms or roles are stored
+    /// as a pending proposal until every affected party has approved it.
     pub fn change_escrow_properties(
         e: &Env,
         platform_address: &Address,
-        escrow_properties: Escrow,
-    ) -> Result<Escrow, ContractError> {
+        mut escrow_properties: Escrow,
+    ) -> Result<Option<UpdateProposal>, ContractError> {
         platform_address.require_auth();
         let existing_escrow = Self::get_escrow(e)?;
-        let token_client = TokenClient::new(e, &existing_escrow.trustline.address);
-        let contract_balance = token_client.balance(&e.current_contract_address());
+        Self::resolve_trustlines(e, &mut escrow_properties)?;
+        Self::validate_property_change(e, &existing_escrow, &escrow_properties, platform_address)?;
+
+        let required_approvals =
+            Self::get_required_update_approvals(e, &existing_escrow, &escrow_properties);
+        if required_approvals.is_empty() {
+            save_escrow(e, &escrow_properties);
+            HistoryManager::record(e, AuditAction::PropertiesChanged, platform_address);
+            return Ok(None);
+        }
+
+        let proposal = UpdateProposal {
+            proposer: platform_address.clone(),
+            escrow: escrow_properties,
+            required_approvals,
+            approvals: Vec::new(e),
+            expiration_ledger: e.ledger().sequence() + UPDATE_PROPOSAL_TTL_LEDGERS,
+        };
+        save_pending_update(e, &proposal);
+        HistoryManager::record(e, AuditAction::UpdateProposed, platform_address);
+        Ok(Some(proposal))
+    }
+
+    /// Records the signer's approval of the pending update and applies it once every
+    /// required party has approved. Returns the applied escrow, if any.
+    pub fn approve_escrow_update(
+        e: &Env,
+        signer: &Address,
+    ) -> Result<(UpdateProposal, Option<Escrow>), ContractError> {
+        signer.require_auth();
+        let pending_update = Self::get_pending_update(e);
+        validate_update_approval(&pending_update, e.ledger().sequence(), signer)?;
+
+        let mut proposal = pending_update.ok_or(ContractError::NoPendingProposal)?;
+        if !proposal.approvals.contains(signer) {
+            proposal.approvals.push_back(signer.clone());
+        }
+        HistoryManager::record(e, AuditAction::UpdateApproved, signer);
+
+        // Approvals are checked against the parties the change affects today
+        let existing_escrow = Self::get_escrow(e)?;
+        let required_approvals =
+            Self::get_required_update_approvals(e, &existing_escrow, &proposal.escrow);
+        let fully_approved = required_approvals
+            .iter()
+            .all(|address| proposal.approvals.contains(&address));
+        if !fully_approved {
+            proposal.required_approvals = required_approvals;
+            save_pending_update(e, &proposal);
+            return Ok((proposal, None));
+        }
+
+        // The escrow may have been funded since the proposal was created
+        Self::validate_property_change(e, &existing_escrow, &proposal.escrow, &proposal.proposer)?;
+
+        save_escrow(e, &proposal.escrow);
+        HistoryManager::record(e, AuditAction::PropertiesChanged, &proposal.proposer);
+        let applied_escrow = proposal.escrow.clone();
+        Ok((proposal, Some(applied_escrow)))
+    }
+
+    pub fn get_pending_update(e: &Env) -> Option<UpdateProposal> {
+        load_pending_update(e)
+            .filter(|proposal| proposal.expiration_ledger >= e.ledger().sequence())
+    }
+
+    /// Fills every leg's trustline from the token itself, so the stored decimals and symbol
+    /// are what the token reports rather than what the caller claimed.
+    fn resolve_trustlines(e: &Env, escrow: &mut Escrow) -> Result<(), ContractError> {
+        escrow.trustline = Self::resolve_trustline(e, &escrow.trustline.address)?;
+
+        let mut extra_legs: Vec<TokenLeg> = Vec::new(e);
+        for leg in escrow.extra_legs.iter() {
+            extra_legs.push_back(TokenLeg {
+                trustline: Self::resolve_trustline(e, &leg.trustline.address)?,
+                amount: leg.amount,
+            });
+        }
+        escrow.extra_legs = extra_legs;
+        Ok(())
+    }
+
+    fn resolve_trustline(e: &Env, token: &Address) -> Result<Trustline, ContractError> {
+        // Anything that doesn't answer the token interface is not a token
+        let token_client = TokenClient::new(e, token);
+        let (Ok(Ok(decimals)), Ok(Ok(symbol))) =
+            (token_client.try_decimals(), token_client.try_symbol())
+        else {
+            return Err(ContractError::InvalidTrustlineToken);
+        };
+        validate_trustline(decimals)?;
+
+        Ok(Trustline {
+            address: token.clone(),
+            decimals,
+            symbol,
+        })
+    }
+
+    fn validate_property_change(
+        e: &Env,
+        existing_escrow: &Escrow,
+        new_escrow: &Escrow,
+        platform_address: &Address,
+    ) -> Result<(), ContractError> {
+        // Funds held in any leg block the change
+        let mut contract_balance: i128 = 0;
+        for leg in Self::get_token_legs(e, existing_escrow).iter() {
+            let token_client = TokenClient::new(e, &leg.trustline.address);
+            let balance = token_client.balance(&e.current_contract_address());
+            contract_balance = BasicMath::safe_add(contract_balance, balance)?;
+        }
 
         validate_escrow_property_change_conditions(
-            &existing_escrow,
-            &escrow_properties,
+            existing_escrow,
+            new_escrow,
             platform_address,
             contract_balance,
+            MilestoneManager::get_approved_milestone_count(e),
+            &AdminManager::get_fee_limits(e, &new_escrow.roles.platform_address),
+            &AdminManager::get_token_allowlist(e, &new_escrow.roles.platform_address),
+        )
+    }
+
+    /// Economic terms need the approver, service provider and receiver to consent, and each
+    /// replaced role needs its current holder. The platform is the proposer and is skipped.
+    fn get_required_update_approvals(
+        e: &Env,
+        existing_escrow: &Escrow,
+        new_escrow: &Escrow,
+    ) -> Vec<Address> {
+        let existing_roles = &existing_escrow.roles;
+        let new_roles = &new_escrow.roles;
+        let mut affected: Vec<Address> = Vec::new(e);
+
+        let economic_terms_changed = existing_escrow.amount != new_escrow.amount
+            || existing_escrow.platform_fee != new_escrow.platform_fee
+            || existing_escrow.fee_mode != new_escrow.fee_mode
+            || existing_escrow.fee_schedule != new_escrow.fee_schedule
+            || existing_escrow.extra_legs != new_escrow.extra_legs
+            || existing_escrow.trustline != new_escrow.trustline
+            || existing_escrow.receiver_memo != new_escrow.receiver_memo
+            || existing_escrow.payees != new_escrow.payees;
+        if economic_terms_changed {
+            affected.push_back(existing_roles.approver.clone());
+            affected.push_back(existing_roles.service_provider.clone());
+            affected.push_back(existing_roles.receiver.clone());
+        }
+        // Payees whose share shrinks or disappears must agree to it
+        if existing_escrow.payees != new_escrow.payees {
+            for payee in existing_escrow.payees.iter() {
+                affected.push_back(payee.address);
+            }
+        }
+
+        let role_changes = [
+            (&existing_roles.approver, &new_roles.approver),
+            (
+                &existing_roles.service_provider,
+                &new_roles.service_provider,
+            ),
+            (&existing_roles.release_signer, &new_roles.release_signer),
+            (
+                &existing_roles.dispute_resolver,
+                &new_roles.dispute_resolver,
+            ),
+            (&existing_roles.receiver, &new_roles.receiver),
+        ];
+        for (current_holder, new_holder) in role_changes {
+            if current_holder != new_holder {
+                affected.push_back(current_holder.clone());
+            }
+        }
+
+        let mut required_approvals: Vec<Address> = Vec::new(e);
+        for address in affected.iter() {
+            if address != existing_roles.platform_address && !required_approvals.contains(&address)
+            {
+                required_approvals.push_back(address);
+            }
+        }
+        required_approvals
+    }
+
+    /// Scope or economic changes made outside a proposal must be co-signed by the
+    /// approver, service provider and receiver in the same transaction.
+    pub fn require_counterparty_consent(e: &Env, escrow: &Escrow) {
+        let roles = &escrow.roles;
+        let mut signers: Vec<Address> = Vec::new(e);
+        for party in [&roles.approver, &roles.service_provider, &roles.receiver] {
+            if party != &roles.platform_address && !signers.contains(party) {
+                signers.push_back(party.clone());
+            }
+        }
+
+        for signer in signers.iter() {
+            signer.require_auth();
+        }
+    }
+
+    pub fn set_title_description(
+        e: &Env,
+        platform_address: &Address,
+        title: String,
+        description: String,
+    ) -> Result<(Escrow, Escrow), ContractError> {
+        platform_address.require_auth();
+        let previous_escrow = Self::get_escrow(e)?;
+        validate_partial_update_conditions(&previous_escrow, platform_address)?;
+
+        let mut escrow = previous_escrow.clone();
+        escrow.title = title;
+        escrow.description = description;
+        save_escrow(e, &escrow);
+        HistoryManager::record(e, AuditAction::TitleDescriptionSet, platform_address);
+
+        Ok((previous_escrow, escrow))
+    }
+
+    /// Sets the amount of the leg paid in `token`, checked against that token's balance.
+    pub fn set_amount(
+        e: &Env,
+        platform_address: &Address,
+        token: &Address,
+        new_amount: i128,
+    ) -> Result<(i128, Escrow), ContractError> {
+        platform_address.require_auth();
+        let mut escrow = Self::get_escrow(e)?;
+        validate_partial_update_conditions(&escrow, platform_address)?;
+        Self::require_counterparty_consent(e, &escrow);
+
+        let leg = Self::get_token_legs(e, &escrow)
+            .iter()
+            .find(|leg| &leg.trustline.address == token)
+            .ok_or(ContractError::TokenNotInEscrow)?;
+        validate_leg_amount(new_amount, leg.trustline.decimals)?;
+        let fee_result = Self::calculate_release_fees(e, &escrow, token, new_amount)?;
+        let token_client = TokenClient::new(e, token);
+        let contract_balance = token_client.balance(&e.current_contract_address());
+        validate_amount_change_conditions(
+            new_amount,
+            BasicMath::safe_add(new_amount, fee_result.funder_surcharge)?,
+            contract_balance,
+            MilestoneManager::get_approved_milestone_count(e),
         )?;
 
-        e.storage()
-            .instance()
-            .set(&DataKey::Escrow, &escrow_properties);
-        Ok(escrow_properties)
+        let previous_amount = leg.amount;
+        if token == &escrow.trustline.address {
+            escrow.amount = new_amount;
+        } else {
+            let mut extra_legs: Vec<TokenLeg> = Vec::new(e);
+            for mut extra_leg in escrow.extra_legs.iter() {
+                if &extra_leg.trustline.address == token {
+                    extra_leg.amount = new_amount;
+                }
+                extra_legs.push_back(extra_leg);
+            }
+            escrow.extra_legs = extra_legs;
+        }
+        // Absolute minimum fees weigh more on a smaller amount
+        validate_fees(
+            &escrow,
+            &AdminManager::get_fee_limits(e, &escrow.roles.platform_address),
+        )?;
+        save_escrow(e, &escrow);
+        HistoryManager::record_leg(
+            e,
+            AuditAction::AmountSet,
+            platform_address,
+            token,
+            new_amount,
+        );
+
+        Ok((previous_amount, escrow))
     }
 
     pub fn get_multiple_escrow_balances(
         e: &Env,
         addresses: Vec<Address>,
     ) -> Result<Vec<AddressBalance>, ContractError> {
-        const MAX_ESCROWS: u32 = 20;
-        if addresses.len() > MAX_ESCROWS {
-            return Err(ContractError::TooManyEscrowsRequested);
-        }
+        validate_batch_size(addresses.len(), AdminManager::get_max_batch_size(e))?;
 
+        // A failing address is reported in its own entry instead of aborting the batch
         let mut balances: Vec<AddressBalance> = Vec::new(e);
-        let self_addr = e.current_contract_address();
         for address in addresses.iter() {
-            let escrow = if address == self_addr {
-                Self::get_escrow(e)?
+            let balance =
+                Self::get_address_balance(e, &address).unwrap_or_else(|error| AddressBalance {
+                    address: address.clone(),
+                    balance: 0,
+                    trustline_decimals: 0,
+                    error_code: Some(error as u32),
+                });
+            balances.push_back(balance);
+        }
+        Ok(balances)
+    }
+
+    fn get_address_balance(e: &Env, address: &Address) -> Result<AddressBalance, ContractError> {
+        let escrow = if *address == e.current_contract_address() {
+            Self::get_escrow(e)?
+        } else {
+            Self::get_escrow_by_contract_id(e, address)?
+        };
+
+        let token_client = TokenClient::new(e, &escrow.trustline.address);
+        let balance = match token_client.try_balance(address) {
+            Ok(Ok(balance)) => balance,
+            _ => return Err(ContractError::InvalidTrustlineToken),
+        };
+        let trustline_decimals = match token_client.try_decimals() {
+            Ok(Ok(decimals)) => decimals,
+            _ => return Err(ContractError::InvalidTrustlineToken),
+        };
+
+        Ok(AddressBalance {
+            address: address.clone(),
+            balance,
+            trustline_decimals,
+            error_code: None,
+        })
+    }
+
+    pub fn get_escrow_summary(e: &Env) -> Result<EscrowSummary, ContractError> {
+        let escrow = Self::get_escrow(e)?;
+        let contract_address = e.current_contract_address();
+        let legs = Self::leg_funding(e, &escrow)?;
+        let first_leg = legs.get_unchecked(0);
+        let total_funded = first_leg.total_funded;
+        let required_funding = first_leg.required_funding;
+        let funding_percentage = if required_funding > 0 {
+            SafeMath::safe_mul_div(total_funded, 100, required_funding)?.clamp(0, u32::MAX as i128)
+                as u32
+        } else {
+            0
+        };
+
+        Ok(EscrowSummary {
+            address: contract_address,
+            engagement_id: escrow.engagement_id,
+            flags: escrow.flags,
+            amount: escrow.amount,
+            required_funding,
+            total_funded,
+            funding_percentage,
+            milestone_count: MilestoneManager::get_milestone_count(e),
+            approved_milestone_count: MilestoneManager::get_approved_milestone_count(e),
+            platform_fee: escrow.platform_fee,
+            protocol_fee_bps: AdminManager::get_platform_protocol_fee(
+                e,
+                &escrow.roles.platform_address,
+            ),
+            trustline: escrow.trustline.address,
+            roles: escrow.roles,
+            legs,
+        })
+    }
+
+    pub fn get_status(e: &Env) -> Result<EscrowStatus, ContractError> {
+        let escrow = Self::get_escrow(e)?;
+
+        Ok(Self::compute_status(
+            &escrow,
+            &Self::leg_funding(e, &escrow)?,
+            MilestoneManager::get_milestone_count(e),
+            MilestoneManager::get_approved_milestone_count(e),
+        ))
+    }
+
+    /// `ReadyToRelease` is reported exactly when `release_funds` would accept the release
+    /// signer, apart from the pause switches which are exposed separately. An escrow is
+    /// funded once every leg is.
+    fn compute_status(
+        escrow: &Escrow,
+        funding: &Vec<LegFunding>,
+        milestone_count: u32,
+        approved_milestone_count: u32,
+    ) -> EscrowStatus {
+        let has_funds = funding.iter().any(|leg| leg.total_funded > 0);
+        if escrow.flags.released || escrow.flags.resolved {
+            return match (has_funds, escrow.flags.released) {
+                (false, _) => EscrowStatus::Closed,
+                (true, true) => EscrowStatus::Released,
+                (true, false) => EscrowStatus::Resolved,
+            };
+        }
+
+        if escrow.flags.disputed {
+            return EscrowStatus::Disputed;
+        }
+
+        if !has_funds {
+            return EscrowStatus::AwaitingFunding;
+        }
+
+        if funding
+            .iter()
+            .any(|leg| leg.total_funded < leg.required_funding)
+        {
+            return EscrowStatus::PartiallyFunded;
+        }
+
+        let releasable = validate_release_conditions(
+            escrow,
+            &escrow.roles.release_signer,
+            milestone_count,
+            approved_milestone_count,
+        );
+        match releasable {
+            Ok(()) => EscrowStatus::ReadyToRelease,
+            Err(_) if approved_milestone_count > 0 => EscrowStatus::AwaitingApproval,
+            Err(_) => EscrowStatus::Active,
+        }
+    }
+
+    pub fn get_escrow_summaries(
+        e: &Env,
+        addresses: Vec<Address>,
+    ) -> Result<Vec<EscrowSummaryEntry>, ContractError> {
+        validate_batch_size(addresses.len(), AdminManager::get_max_batch_size(e))?;
+
+        let mut summaries: Vec<EscrowSummaryEntry> = Vec::new(e);
+        for address in addresses.iter() {
+            let summary = if address == e.current_contract_address() {
+                Self::get_escrow_summary(e)
             } else {
-                Self::get_escrow_by_contract_id(e, &address)?
+                Self::get_escrow_summary_by_contract_id(e, &address)
             };
-            let token_client = TokenClient::new(e, &escrow.trustline.address);
-            let balance = token_client.balance(&address);
-            balances.push_back(AddressBalance {
-                address: address.clone(),
-                balance,
-                trustline_decimals: token_client.decimals(),
+            summaries.push_back(match summary {
+                Ok(summary) => EscrowSummaryEntry::Summary(summary),
+                Err(error) => EscrowSummaryEntry::Failed(address, error as u32),
             });
         }
-        Ok(balances)
+        Ok(summaries)
+    }
+
+    fn get_escrow_summary_by_contract_id(
+        e: &Env,
+        contract_id: &Address,
+    ) -> Result<EscrowSummary, ContractError> {
+        match e.try_invoke_contract::<EscrowSummary, ContractError>(
+            contract_id,
+            &Symbol::new(e, "get_escrow_summary"),
+            Vec::new(e),
+        ) {
+            Ok(Ok(summary)) => Ok(summary),
+            Err(Ok(error)) => Err(error),
+            _ => Err(ContractError::InvalidEscrowContract),
+        }
     }
 
+    /// Reads another escrow's state without letting a bad address abort the caller. Errors
+    /// raised by the target escrow are passed through; anything else means the address is
+    /// not an escrow contract.
     pub fn get_escrow_by_contract_id(
         e: &Env,
         contract_id: &Address,
     ) -> Result<Escrow, ContractError> {
-        Ok(e.invoke_contract::<Escrow>(contract_id, &Symbol::new(e, "get_escrow"), Vec::new(e)))
+        match e.try_invoke_contract::<Escrow, ContractError>(
+            contract_id,
+            &Symbol::new(e, "get_escrow"),
+            Vec::new(e),
+        ) {
+            Ok(Ok(escrow)) => Ok(escrow),
+            Err(Ok(error)) => Err(error),
+            _ => Err(ContractError::InvalidEscrowContract),
+        }
+    }
+
+    /// Engagement id used to tag events; empty until the escrow is initialized.
+    pub fn get_engagement_id(e: &Env) -> String {
+        Self::get_escrow(e)
+            .map(|escrow| escrow.engagement_id)
+            .unwrap_or_else(|_| String::from_str(e, ""))
     }
 
     pub fn get_escrow(e: &Env) -> Result<Escrow, ContractError> {
-        Ok(e.storage()
-            .instance()
-            .get(&DataKey::Escrow)
-            .ok_or(ContractError::EscrowNotFound)?)
+        load_escrow(e)
     }
 }
@@ -0,0 +1,67 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::storage::store::{
+    load_first_history_index, load_history_count, load_history_entry, save_history_entry,
+};
+use crate::storage::types::{AuditAction, AuditEntry};
+
+pub const MAX_HISTORY_PER_PAGE: u32 = 50;
+
+pub struct HistoryManager;
+
+impl HistoryManager {
+    /// Appends an entry that moves no tokens to the audit log. Entries are never edited;
+    /// once the log holds `MAX_HISTORY_ENTRIES` the oldest one is overwritten.
+    pub fn record(e: &Env, action: AuditAction, actor: &Address) {
+        Self::append(e, action, actor, None, 0);
+    }
+
+    /// Appends an entry for `amount` of `token` moved or set by the action.
+    pub fn record_leg(
+        e: &Env,
+        action: AuditAction,
+        actor: &Address,
+        token: &Address,
+        amount: i128,
+    ) {
+        Self::append(e, action, actor, Some(token.clone()), amount);
+    }
+
+    fn append(e: &Env, action: AuditAction, actor: &Address, token: Option<Address>, amount: i128) {
+        let entry = AuditEntry {
+            action,
+            actor: actor.clone(),
+            ledger: e.ledger().sequence(),
+            timestamp: e.ledger().timestamp(),
+            token,
+            amount,
+        };
+        save_history_entry(e, load_history_count(e), &entry);
+    }
+
+    pub fn get_history_count(e: &Env) -> u32 {
+        load_history_count(e)
+    }
+
+    /// Index of the oldest entry still stored; anything before it has been overwritten.
+    pub fn get_first_history_index(e: &Env) -> u32 {
+        load_first_history_index(e)
+    }
+
+    /// Returns up to `limit` entries starting at `offset`, oldest first, capped at
+    /// `MAX_HISTORY_PER_PAGE` per call. Offsets count every entry ever recorded, so a page
+    /// that starts before `get_first_history_index` only returns the entries still stored.
+    pub fn get_history(e: &Env, offset: u32, limit: u32) -> Vec<AuditEntry> {
+        let end = offset
+            .saturating_add(limit.min(MAX_HISTORY_PER_PAGE))
+            .min(load_history_count(e));
+
+        let mut entries: Vec<AuditEntry> = Vec::new(e);
+        for entry_index in offset..end {
+            if let Some(entry) = load_history_entry(e, entry_index) {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+}
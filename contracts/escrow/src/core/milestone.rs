@@ -1,46 +1,92 @@
 use crate::error::ContractError;
-use crate::storage::types::DataKey;
-use crate::{core::escrow::EscrowManager, storage::types::Escrow};
-use soroban_sdk::{Address, Env, String};
+use crate::storage::store::{
+    load_approved_milestone_count, load_milestone, load_milestone_count, remove_milestone,
+    save_approved_milestone_count, save_milestone, save_milestone_count,
+};
+use crate::{
+    core::{escrow::EscrowManager, history::HistoryManager},
+    storage::types::{AuditAction, Escrow, Milestone},
+};
+use soroban_sdk::{Address, Env, String, Vec};
 
+use super::validators::escrow::validate_partial_update_conditions;
 use super::validators::milestone::{
-    validate_milestone_flag_change_conditions, validate_milestone_status_change_conditions,
+    validate_milestone_addition_conditions, validate_milestone_description_change_conditions,
+    validate_milestone_flag_change_conditions, validate_milestone_removal_conditions,
+    validate_milestone_status_change_conditions,
 };
 
+pub const MAX_MILESTONES_PER_PAGE: u32 = 50;
+
 pub struct MilestoneManager;
 
 impl MilestoneManager {
+    pub fn get_milestone_count(e: &Env) -> u32 {
+        load_milestone_count(e)
+    }
+
+    pub fn get_approved_milestone_count(e: &Env) -> u32 {
+        load_approved_milestone_count(e)
+    }
+
+    pub fn get_milestone(e: &Env, milestone_index: i128) -> Result<Milestone, ContractError> {
+        if milestone_index < 0 || milestone_index >= Self::get_milestone_count(e) as i128 {
+            return Err(ContractError::InvalidMileStoneIndex);
+        }
+
+        load_milestone(e, milestone_index as u32)
+    }
+
+    /// Returns up to `limit` milestones starting at `offset`, capped at
+    /// `MAX_MILESTONES_PER_PAGE` per call.
+    pub fn get_milestones(e: &Env, offset: u32, limit: u32) -> Vec<Milestone> {
+        let count = Self::get_milestone_count(e);
+        let end = offset
+            .saturating_add(limit.min(MAX_MILESTONES_PER_PAGE))
+            .min(count);
+
+        let mut milestones: Vec<Milestone> = Vec::new(e);
+        for milestone_index in offset..end {
+            if let Ok(milestone) = load_milestone(e, milestone_index) {
+                milestones.push_back(milestone);
+            }
+        }
+        milestones
+    }
+
+    pub fn store_initial_milestones(e: &Env, milestones: &Vec<Milestone>) {
+        for (milestone_index, milestone) in milestones.iter().enumerate() {
+            save_milestone(e, milestone_index as u32, &milestone);
+        }
+        save_milestone_count(e, milestones.len());
+        save_approved_milestone_count(e, 0);
+    }
+
     pub fn change_milestone_status(
         e: &Env,
         milestone_index: i128,
         new_status: String,
         new_evidence: Option<String>,
         service_provider: Address,
-    ) -> Result<Escrow, ContractError> {
+    ) -> Result<(Escrow, String), ContractError> {
         service_provider.require_auth();
-        let mut existing_escrow = EscrowManager::get_escrow(e)?;
+        let existing_escrow = EscrowManager::get_escrow(e)?;
 
         validate_milestone_status_change_conditions(&existing_escrow, &service_provider)?;
 
-        let mut milestone_to_update = existing_escrow
-            .milestones
-            .get(milestone_index as u32)
-            .ok_or(ContractError::InvalidMileStoneIndex)?;
+        let mut milestone_to_update = Self::get_milestone(e, milestone_index)?;
 
         if let Some(evidence) = new_evidence {
             milestone_to_update.evidence = evidence;
         }
 
+        let previous_status = milestone_to_update.status.clone();
         milestone_to_update.status = new_status;
 
-        existing_escrow
-            .milestones
-            .set(milestone_index as u32, milestone_to_update);
-        e.storage()
-            .instance()
-            .set(&DataKey::Escrow, &existing_escrow);
+        save_milestone(e, milestone_index as u32, &milestone_to_update);
+        HistoryManager::record(e, AuditAction::MilestoneStatusChanged, &service_provider);
 
-        Ok(existing_escrow)
+        Ok((existing_escrow, previous_status))
     }
 
     pub fn change_milestone_approved_flag(
@@ -49,12 +95,9 @@ impl MilestoneManager {
         approver: Address,
     ) -> Result<Escrow, ContractError> {
         approver.require_auth();
-        let mut existing_escrow = EscrowManager::get_escrow(e)?;
+        let existing_escrow = EscrowManager::get_escrow(e)?;
 
-        let mut milestone_to_update = existing_escrow
-            .milestones
-            .get(milestone_index as u32)
-            .ok_or(ContractError::InvalidMileStoneIndex)?;
+        let mut milestone_to_update = Self::get_milestone(e, milestone_index)?;
 
         validate_milestone_flag_change_conditions(
             &existing_escrow,
@@ -63,13 +106,96 @@ impl MilestoneManager {
         )?;
         milestone_to_update.approved = true;
 
-        existing_escrow
-            .milestones
-            .set(milestone_index as u32, milestone_to_update);
-        e.storage()
-            .instance()
-            .set(&DataKey::Escrow, &existing_escrow);
+        save_milestone(e, milestone_index as u32, &milestone_to_update);
+        save_approved_milestone_count(e, Self::get_approved_milestone_count(e) + 1);
+        HistoryManager::record(e, AuditAction::MilestoneApproved, &approver);
 
         Ok(existing_escrow)
     }
+
+    pub fn add_milestone(
+        e: &Env,
+        platform_address: Address,
+        milestone: Milestone,
+    ) -> Result<(u32, Escrow), ContractError> {
+        Self::add_milestones(e, platform_address, Vec::from_array(e, [milestone]))
+    }
+
+    /// Appends `milestones` in order, at most `MAX_MILESTONES_PER_CALL` at a time. Returns
+    /// the index of the first one.
+    pub fn add_milestones(
+        e: &Env,
+        platform_address: Address,
+        milestones: Vec<Milestone>,
+    ) -> Result<(u32, Escrow), ContractError> {
+        platform_address.require_auth();
+        let existing_escrow = EscrowManager::get_escrow(e)?;
+        let milestone_count = Self::get_milestone_count(e);
+        validate_partial_update_conditions(&existing_escrow, &platform_address)?;
+        validate_milestone_addition_conditions(&milestones, milestone_count)?;
+        EscrowManager::require_counterparty_consent(e, &existing_escrow);
+
+        for (offset, milestone) in milestones.iter().enumerate() {
+            save_milestone(e, milestone_count + offset as u32, &milestone);
+            HistoryManager::record(e, AuditAction::MilestoneAdded, &platform_address);
+        }
+        save_milestone_count(e, milestone_count + milestones.len());
+
+        Ok((milestone_count, existing_escrow))
+    }
+
+    /// Removes a milestone by moving the last one into its index, so the call writes a
+    /// constant number of entries however many milestones the escrow has. Returns the
+    /// index the moved milestone came from.
+    pub fn remove_milestone(
+        e: &Env,
+        platform_address: Address,
+        milestone_index: i128,
+    ) -> Result<(Milestone, u32, Escrow), ContractError> {
+        platform_address.require_auth();
+        let existing_escrow = EscrowManager::get_escrow(e)?;
+        let milestone_count = Self::get_milestone_count(e);
+        validate_partial_update_conditions(&existing_escrow, &platform_address)?;
+
+        let removed_milestone = Self::get_milestone(e, milestone_index)?;
+        validate_milestone_removal_conditions(&removed_milestone, milestone_count)?;
+        EscrowManager::require_counterparty_consent(e, &existing_escrow);
+
+        let last_index = milestone_count - 1;
+        if milestone_index as u32 != last_index {
+            let last_milestone = load_milestone(e, last_index)?;
+            save_milestone(e, milestone_index as u32, &last_milestone);
+        }
+        remove_milestone(e, last_index);
+        save_milestone_count(e, milestone_count - 1);
+        HistoryManager::record(e, AuditAction::MilestoneRemoved, &platform_address);
+
+        Ok((removed_milestone, last_index, existing_escrow))
+    }
+
+    pub fn edit_milestone_description(
+        e: &Env,
+        platform_address: Address,
+        milestone_index: i128,
+        new_description: String,
+    ) -> Result<(String, Escrow), ContractError> {
+        platform_address.require_auth();
+        let existing_escrow = EscrowManager::get_escrow(e)?;
+        validate_partial_update_conditions(&existing_escrow, &platform_address)?;
+
+        let mut milestone_to_update = Self::get_milestone(e, milestone_index)?;
+        validate_milestone_description_change_conditions(&milestone_to_update)?;
+        EscrowManager::require_counterparty_consent(e, &existing_escrow);
+
+        let previous_description = milestone_to_update.description.clone();
+        milestone_to_update.description = new_description;
+        save_milestone(e, milestone_index as u32, &milestone_to_update);
+        HistoryManager::record(
+            e,
+            AuditAction::MilestoneDescriptionChanged,
+            &platform_address,
+        );
+
+        Ok((previous_description, existing_escrow))
+    }
 }
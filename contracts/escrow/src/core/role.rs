@@ -0,0 +1,92 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::core::escrow::EscrowManager;
+use crate::core::history::HistoryManager;
+use crate::error::ContractError;
+use crate::storage::store::{load_instance, remove_instance, save_escrow, save_instance};
+use crate::storage::ttl::DAY_IN_LEDGERS;
+use crate::storage::types::{AuditAction, DataKey, Escrow, Role, RoleChangeProposal, Roles};
+
+use super::validators::role::{validate_role_change_acceptance, validate_role_change_proposal};
+
+/// Ledgers a pending role change stays open for.
+pub const ROLE_CHANGE_PROPOSAL_TTL_LEDGERS: u32 = DAY_IN_LEDGERS;
+
+pub struct RoleManager;
+
+impl RoleManager {
+    pub fn get_role_address(roles: &Roles, role: Role) -> Address {
+        match role {
+            Role::Approver => roles.approver.clone(),
+            Role::ServiceProvider => roles.service_provider.clone(),
+            Role::PlatformAddress => roles.platform_address.clone(),
+            Role::ReleaseSigner => roles.release_signer.clone(),
+            Role::DisputeResolver => roles.dispute_resolver.clone(),
+            Role::Receiver => roles.receiver.clone(),
+        }
+    }
+
+    fn set_role_address(roles: &mut Roles, role: Role, address: Address) {
+        match role {
+            Role::Approver => roles.approver = address,
+            Role::ServiceProvider => roles.service_provider = address,
+            Role::PlatformAddress => roles.platform_address = address,
+            Role::ReleaseSigner => roles.release_signer = address,
+            Role::DisputeResolver => roles.dispute_resolver = address,
+            Role::Receiver => roles.receiver = address,
+        }
+    }
+
+    pub fn propose_role_change(
+        e: &Env,
+        proposers: &Vec<Address>,
+        role: Role,
+        new_address: &Address,
+    ) -> Result<RoleChangeProposal, ContractError> {
+        for proposer in proposers.iter() {
+            proposer.require_auth();
+        }
+
+        let escrow = EscrowManager::get_escrow(e)?;
+        let current_holder = Self::get_role_address(&escrow.roles, role);
+        validate_role_change_proposal(&escrow, role, &current_holder, proposers)?;
+
+        let proposal = RoleChangeProposal {
+            new_address: new_address.clone(),
+            expiration_ledger: e.ledger().sequence() + ROLE_CHANGE_PROPOSAL_TTL_LEDGERS,
+        };
+        save_instance(e, &DataKey::PendingRoleChange(role), &proposal);
+        Ok(proposal)
+    }
+
+    pub fn accept_role_change(
+        e: &Env,
+        role: Role,
+        new_address: &Address,
+    ) -> Result<(Escrow, Address), ContractError> {
+        new_address.require_auth();
+        let mut escrow = EscrowManager::get_escrow(e)?;
+        validate_role_change_acceptance(
+            &escrow,
+            load_instance(e, &DataKey::PendingRoleChange(role)),
+            e.ledger().sequence(),
+            new_address,
+        )?;
+
+        let previous_address = Self::get_role_address(&escrow.roles, role);
+        Self::set_role_address(&mut escrow.roles, role, new_address.clone());
+
+        save_escrow(e, &escrow);
+        remove_instance(e, &DataKey::PendingRoleChange(role));
+        HistoryManager::record(e, AuditAction::RoleChanged, new_address);
+
+        Ok((escrow, previous_address))
+    }
+
+    /// The open proposal for `role`; expired proposals are ignored.
+    pub fn get_pending_role_change(e: &Env, role: Role) -> Option<RoleChangeProposal> {
+        load_instance(e, &DataKey::PendingRoleChange(role)).filter(
+            |proposal: &RoleChangeProposal| proposal.expiration_ledger >= e.ledger().sequence(),
+        )
+    }
+}
@@ -0,0 +1,96 @@
+use soroban_sdk::Address;
+
+use crate::{
+    core::admin::MAX_BATCH_SIZE_LIMIT,
+    error::ContractError,
+    modules::fee::{MAX_MIN_RECEIVER_SHARE_BPS, MAX_PLATFORM_FEE_BPS, MAX_PROTOCOL_FEE_BPS},
+};
+
+#[inline]
+pub fn validate_admin(admin: &Address, signer: &Address) -> Result<(), ContractError> {
+    if signer != admin {
+        return Err(ContractError::OnlyAdminCanExecuteThisFunction);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_proposal_acceptance(
+    pending_address: Option<Address>,
+    new_address: &Address,
+) -> Result<(), ContractError> {
+    let pending_address = pending_address.ok_or(ContractError::NoPendingProposal)?;
+
+    if new_address != &pending_address {
+        return Err(ContractError::OnlyProposedAddressCanAccept);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_protocol_fee(fee_bps: u32) -> Result<(), ContractError> {
+    if fee_bps > MAX_PROTOCOL_FEE_BPS {
+        return Err(ContractError::ProtocolFeeTooHigh);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_max_platform_fee(max_platform_fee_bps: u32) -> Result<(), ContractError> {
+    if max_platform_fee_bps > MAX_PLATFORM_FEE_BPS {
+        return Err(ContractError::PlatformFeeTooHigh);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_min_receiver_share(min_receiver_share_bps: u32) -> Result<(), ContractError> {
+    if min_receiver_share_bps == 0 || min_receiver_share_bps > MAX_MIN_RECEIVER_SHARE_BPS {
+        return Err(ContractError::InvalidMinReceiverShare);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_max_batch_size(max_batch_size: u32) -> Result<(), ContractError> {
+    if max_batch_size == 0 || max_batch_size > MAX_BATCH_SIZE_LIMIT {
+        return Err(ContractError::InvalidMaxBatchSize);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_not_paused(
+    protocol_paused: bool,
+    escrow_paused: bool,
+) -> Result<(), ContractError> {
+    if protocol_paused {
+        return Err(ContractError::ProtocolPaused);
+    }
+
+    if escrow_paused {
+        return Err(ContractError::EscrowPaused);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_trustless_work_address(
+    fee_collector: &Option<Address>,
+    trustless_work_address: &Address,
+) -> Result<(), ContractError> {
+    if let Some(fee_collector) = fee_collector {
+        if fee_collector != trustless_work_address {
+            return Err(ContractError::InvalidTrustlessWorkAddress);
+        }
+    }
+
+    Ok(())
+}
@@ -1,14 +1,47 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Vec};
 
 use crate::{
     error::ContractError,
-    storage::types::{DataKey, Escrow},
+    modules::{
+        fee::{FeeCalculator, FeeCalculatorTrait, FeeLimits, BASIS_POINTS_DENOMINATOR},
+        math::{SafeArithmetic, SafeMath},
+    },
+    storage::{
+        store::has_escrow,
+        types::{Escrow, FeeSchedule, Milestone, Payee, UpdateProposal},
+    },
 };
 
+pub const MAX_MILESTONES: u32 = 200;
+/// Each milestone is its own ledger entry, so `initialize_escrow` and `add_milestones` write
+/// at most this many per call to stay within the per-transaction write limit.
+pub const MAX_MILESTONES_PER_CALL: u32 = 20;
+pub const MAX_PAYEES: u32 = 20;
+/// Tokens per escrow, the first leg included; each one is a transfer batch on release.
+pub const MAX_TOKEN_LEGS: u32 = 5;
+/// Amounts are in the token's smallest unit; more decimals leave too little headroom in
+/// i128 for the fee math.
+pub const MAX_TRUSTLINE_DECIMALS: u32 = 18;
+/// Largest leg amount in whole tokens. At `MAX_TRUSTLINE_DECIMALS` this is 10^33 units,
+/// which keeps basis point products well inside i128.
+pub const MAX_WHOLE_TOKEN_AMOUNT: i128 = 1_000_000_000_000_000;
+const PAYEE_SHARES_TOTAL_BPS: u32 = 10000;
+
+#[inline]
+pub fn validate_batch_size(requested: u32, max_batch_size: u32) -> Result<(), ContractError> {
+    if requested > max_batch_size {
+        return Err(ContractError::TooManyEscrowsRequested);
+    }
+
+    Ok(())
+}
+
 #[inline]
 pub fn validate_release_conditions(
     escrow: &Escrow,
     release_signer: &Address,
+    milestone_count: u32,
+    approved_milestone_count: u32,
 ) -> Result<(), ContractError> {
     if escrow.flags.released {
         return Err(ContractError::EscrowAlreadyReleased);
@@ -22,11 +55,11 @@ pub fn validate_release_conditions(
         return Err(ContractError::OnlyReleaseSignerCanReleaseEarnings);
     }
 
-    if escrow.milestones.is_empty() {
+    if milestone_count == 0 {
         return Err(ContractError::NoMilestoneDefined);
     }
 
-    if !escrow.milestones.iter().all(|milestone| milestone.approved) {
+    if approved_milestone_count < milestone_count {
         return Err(ContractError::EscrowNotCompleted);
     }
 
@@ -43,6 +76,9 @@ pub fn validate_escrow_property_change_conditions(
     new_escrow: &Escrow,
     platform_address: &Address,
     contract_balance: i128,
+    approved_milestone_count: u32,
+    fee_limits: &FeeLimits,
+    allowed_tokens: &Option<Vec<Address>>,
 ) -> Result<(), ContractError> {
     if existing_escrow.flags.disputed {
         return Err(ContractError::EscrowOpenedForDisputeResolution);
@@ -52,17 +88,11 @@ pub fn validate_escrow_property_change_conditions(
         return Err(ContractError::PlatformAddressCannotBeChanged);
     }
 
-    for milestone in existing_escrow.milestones.iter() {
-        if milestone.approved {
-            return Err(ContractError::MilestoneApprovedCantChangeEscrowProperties);
-        }
+    if approved_milestone_count > 0 {
+        return Err(ContractError::MilestoneApprovedCantChangeEscrowProperties);
     }
 
-    if new_escrow.flags.released
-        || new_escrow.flags.disputed
-        || new_escrow.flags.resolved
-        || new_escrow.milestones.iter().any(|m| m.approved)
-    {
+    if new_escrow.flags.released || new_escrow.flags.disputed || new_escrow.flags.resolved {
         return Err(ContractError::FlagsMustBeFalse);
     }
 
@@ -78,13 +108,9 @@ pub fn validate_escrow_property_change_conditions(
         return Err(ContractError::AmountCannotBeZero);
     }
 
-    if new_escrow.milestones.len() > 10 {
-        return Err(ContractError::TooManyMilestones);
-    }
-
-    if new_escrow.milestones.is_empty() {
-        return Err(ContractError::NoMilestoneDefined);
-    }
+    validate_token_legs(new_escrow, allowed_tokens)?;
+    validate_fees(new_escrow, fee_limits)?;
+    validate_payees(&new_escrow.payees)?;
 
     Ok(())
 }
@@ -93,34 +119,234 @@ pub fn validate_escrow_property_change_conditions(
 pub fn validate_initialize_escrow_conditions(
     e: &Env,
     escrow_properties: Escrow,
+    milestones: &Vec<Milestone>,
+    fee_limits: &FeeLimits,
+    allowed_tokens: &Option<Vec<Address>>,
 ) -> Result<(), ContractError> {
-    if e.storage().instance().has(&DataKey::Escrow) {
+    if has_escrow(e) {
         return Err(ContractError::EscrowAlreadyInitialized);
     }
 
     if escrow_properties.flags.released
         || escrow_properties.flags.disputed
         || escrow_properties.flags.resolved
-        || escrow_properties.milestones.iter().any(|m| m.approved)
+        || milestones.iter().any(|m| m.approved)
     {
         return Err(ContractError::FlagsMustBeFalse);
     }
 
-    if escrow_properties.milestones.is_empty() {
+    if milestones.is_empty() {
         return Err(ContractError::NoMilestoneDefined);
     }
 
-    let max_bps_percentage: u32 = 99 * 100;
-    if escrow_properties.platform_fee > max_bps_percentage {
-        return Err(ContractError::PlatformFeeTooHigh);
+    if escrow_properties.amount <= 0 {
+        return Err(ContractError::AmountCannotBeZero);
     }
 
-    if escrow_properties.amount == 0 {
+    validate_token_legs(&escrow_properties, allowed_tokens)?;
+    validate_fees(&escrow_properties, fee_limits)?;
+
+    if milestones.len() > MAX_MILESTONES_PER_CALL {
+        return Err(ContractError::TooManyMilestones);
+    }
+
+    validate_payees(&escrow_properties.payees)?;
+
+    Ok(())
+}
+
+/// Every leg needs a positive amount, its own token and a place on the allowlist.
+#[inline]
+pub fn validate_token_legs(
+    escrow: &Escrow,
+    allowed_tokens: &Option<Vec<Address>>,
+) -> Result<(), ContractError> {
+    if escrow.extra_legs.len() + 1 > MAX_TOKEN_LEGS {
+        return Err(ContractError::TooManyTokenLegs);
+    }
+
+    validate_token_allowed(&escrow.trustline.address, allowed_tokens)?;
+    validate_leg_amount(escrow.amount, escrow.trustline.decimals)?;
+    for (index, leg) in escrow.extra_legs.iter().enumerate() {
+        validate_leg_amount(leg.amount, leg.trustline.decimals)?;
+
+        let is_duplicate = leg.trustline.address == escrow.trustline.address
+            || escrow
+                .extra_legs
+                .iter()
+                .take(index)
+                .any(|previous| previous.trustline.address == leg.trustline.address);
+        if is_duplicate {
+            return Err(ContractError::DuplicateTokenLeg);
+        }
+
+        validate_token_allowed(&leg.trustline.address, allowed_tokens)?;
+    }
+
+    Ok(())
+}
+
+/// `None` leaves the token unchecked; a platform with an allowlist, even an empty one,
+/// only accepts the tokens on it.
+#[inline]
+pub fn validate_token_allowed(
+    token: &Address,
+    allowed_tokens: &Option<Vec<Address>>,
+) -> Result<(), ContractError> {
+    if let Some(allowed_tokens) = allowed_tokens {
+        if !allowed_tokens.contains(token) {
+            return Err(ContractError::TokenNotAllowed);
+        }
+    }
+
+    Ok(())
+}
+
+/// `decimals` is what the token reports.
+#[inline]
+pub fn validate_trustline(decimals: u32) -> Result<(), ContractError> {
+    if decimals > MAX_TRUSTLINE_DECIMALS {
+        return Err(ContractError::InvalidTrustlineToken);
+    }
+
+    Ok(())
+}
+
+/// `amount` is in the token's smallest unit, so the cap scales with its decimals.
+#[inline]
+pub fn validate_leg_amount(amount: i128, decimals: u32) -> Result<(), ContractError> {
+    if amount <= 0 {
         return Err(ContractError::AmountCannotBeZero);
     }
 
-    if escrow_properties.milestones.len() > 10 {
-        return Err(ContractError::TooManyMilestones);
+    let max_amount = 10_i128
+        .checked_pow(decimals)
+        .and_then(|unit| unit.checked_mul(MAX_WHOLE_TOKEN_AMOUNT))
+        .ok_or(ContractError::Overflow)?;
+    if amount > max_amount {
+        return Err(ContractError::AmountTooLarge);
+    }
+
+    Ok(())
+}
+
+/// Checks the fee schedule and that protocol and platform fees together leave the receiver
+/// at least `min_receiver_share_bps`, both at the highest rate of the schedule and for the
+/// amount of every leg, where absolute minimum fees also count.
+#[inline]
+pub fn validate_fees(escrow: &Escrow, fee_limits: &FeeLimits) -> Result<(), ContractError> {
+    let max_platform_fee_bps = fee_limits.max_platform_fee_bps;
+    if escrow.platform_fee > max_platform_fee_bps {
+        return Err(ContractError::PlatformFeeTooHigh);
+    }
+
+    let mut highest_rate_bps = escrow.platform_fee;
+    match &escrow.fee_schedule {
+        FeeSchedule::Flat => {}
+        FeeSchedule::Tiered(tiers) => {
+            highest_rate_bps = 0;
+            for (tier_index, tier) in tiers.iter().enumerate() {
+                if tier.up_to <= 0 {
+                    return Err(ContractError::InvalidFeeTier);
+                }
+
+                let duplicate = tiers
+                    .iter()
+                    .skip(tier_index + 1)
+                    .any(|other| other.up_to == tier.up_to);
+                if duplicate {
+                    return Err(ContractError::InvalidFeeTier);
+                }
+
+                if tier.bps > max_platform_fee_bps {
+                    return Err(ContractError::PlatformFeeTooHigh);
+                }
+
+                highest_rate_bps = highest_rate_bps.max(tier.bps);
+            }
+        }
+        FeeSchedule::Bounded(bounds_by_token) => {
+            for (token, bounds) in bounds_by_token.iter() {
+                if bounds.min_fee < 0 || bounds.max_fee <= 0 || bounds.min_fee > bounds.max_fee {
+                    return Err(ContractError::InvalidFeeBounds);
+                }
+
+                let is_leg = escrow.trustline.address == token
+                    || escrow
+                        .extra_legs
+                        .iter()
+                        .any(|leg| leg.trustline.address == token);
+                if !is_leg {
+                    return Err(ContractError::InvalidFeeBounds);
+                }
+            }
+
+            // Every leg is a distinct token, so one entry per leg covers them all
+            if bounds_by_token.len() != escrow.extra_legs.len() + 1 {
+                return Err(ContractError::InvalidFeeBounds);
+            }
+        }
+    }
+
+    let total_rate_bps = fee_limits.protocol_fee_bps + highest_rate_bps;
+    if total_rate_bps + fee_limits.min_receiver_share_bps > BASIS_POINTS_DENOMINATOR as u32 {
+        return Err(ContractError::PlatformFeeTooHigh);
+    }
+
+    let extra_legs = escrow
+        .extra_legs
+        .iter()
+        .map(|leg| (leg.trustline.address, leg.amount));
+    let legs =
+        core::iter::once((escrow.trustline.address.clone(), escrow.amount)).chain(extra_legs);
+    for (token, amount) in legs {
+        let fees = FeeCalculator::for_escrow(escrow, &token).calculate_standard_fees(
+            amount,
+            fee_limits.protocol_fee_bps,
+            max_platform_fee_bps,
+        )?;
+        let min_receiver_amount = SafeMath::safe_mul_div(
+            amount,
+            fee_limits.min_receiver_share_bps,
+            BASIS_POINTS_DENOMINATOR,
+        )?;
+        if fees.receiver_amount < min_receiver_amount {
+            return Err(ContractError::PlatformFeeTooHigh);
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_payees(payees: &Vec<Payee>) -> Result<(), ContractError> {
+    if payees.is_empty() {
+        return Ok(());
+    }
+
+    if payees.len() > MAX_PAYEES {
+        return Err(ContractError::InvalidPayeeSplit);
+    }
+
+    let mut total_bps: u32 = 0;
+    for (index, payee) in payees.iter().enumerate() {
+        if payee.bps == 0 || payee.bps > PAYEE_SHARES_TOTAL_BPS {
+            return Err(ContractError::InvalidPayeeSplit);
+        }
+
+        let is_duplicate = payees
+            .iter()
+            .take(index)
+            .any(|previous| previous.address == payee.address);
+        if is_duplicate {
+            return Err(ContractError::InvalidPayeeSplit);
+        }
+
+        total_bps += payee.bps;
+    }
+
+    if total_bps != PAYEE_SHARES_TOTAL_BPS {
+        return Err(ContractError::InvalidPayeeSplit);
     }
 
     Ok(())
@@ -128,6 +354,7 @@ pub fn validate_initialize_escrow_conditions(
 
 #[inline]
 pub fn validate_fund_escrow_conditions(
+    token: &Address,
     amount: i128,
     stored_escrow: &Escrow,
     expected_escrow: &Escrow,
@@ -136,9 +363,84 @@ pub fn validate_fund_escrow_conditions(
         return Err(ContractError::AmountCannotBeZero);
     }
 
-    if !stored_escrow.eq(&expected_escrow) {
+    if !stored_escrow.eq(expected_escrow) {
         return Err(ContractError::EscrowPropertiesMismatch);
     }
 
+    let is_leg_token = stored_escrow.trustline.address == *token
+        || stored_escrow
+            .extra_legs
+            .iter()
+            .any(|leg| leg.trustline.address == *token);
+    if !is_leg_token {
+        return Err(ContractError::TokenNotInEscrow);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_update_approval(
+    proposal: &Option<UpdateProposal>,
+    current_ledger: u32,
+    signer: &Address,
+) -> Result<(), ContractError> {
+    let proposal = match proposal {
+        Some(proposal) if proposal.expiration_ledger >= current_ledger => proposal,
+        _ => return Err(ContractError::NoPendingProposal),
+    };
+
+    if !proposal.required_approvals.contains(signer) {
+        return Err(ContractError::UnauthorizedProposalSigner);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_partial_update_conditions(
+    escrow: &Escrow,
+    platform_address: &Address,
+) -> Result<(), ContractError> {
+    if platform_address != &escrow.roles.platform_address {
+        return Err(ContractError::OnlyPlatformAddressExecuteThisFunction);
+    }
+
+    if escrow.flags.released {
+        return Err(ContractError::EscrowAlreadyReleased);
+    }
+
+    if escrow.flags.resolved {
+        return Err(ContractError::EscrowAlreadyResolved);
+    }
+
+    if escrow.flags.disputed {
+        return Err(ContractError::EscrowOpenedForDisputeResolution);
+    }
+
+    Ok(())
+}
+
+/// `required_funding` is the new amount plus any fees the fee mode charges on top, which is
+/// the most the escrow may already hold.
+#[inline]
+pub fn validate_amount_change_conditions(
+    new_amount: i128,
+    required_funding: i128,
+    contract_balance: i128,
+    approved_milestone_count: u32,
+) -> Result<(), ContractError> {
+    if new_amount <= 0 {
+        return Err(ContractError::AmountCannotBeZero);
+    }
+
+    if approved_milestone_count > 0 {
+        return Err(ContractError::MilestoneApprovedCantChangeEscrowProperties);
+    }
+
+    if contract_balance > required_funding {
+        return Err(ContractError::EscrowHasFunds);
+    }
+
     Ok(())
 }
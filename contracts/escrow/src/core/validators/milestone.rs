@@ -1,6 +1,7 @@
-use soroban_sdk::Address;
+use soroban_sdk::{Address, Vec};
 
 use crate::{
+    core::validators::escrow::{MAX_MILESTONES, MAX_MILESTONES_PER_CALL},
     error::ContractError,
     storage::types::{Escrow, Milestone},
 };
@@ -14,10 +15,6 @@ pub fn validate_milestone_status_change_conditions(
         return Err(ContractError::OnlyServiceProviderChangeMilstoneStatus);
     }
 
-    if escrow.milestones.is_empty() {
-        return Err(ContractError::NoMilestoneDefined);
-    }
-
     Ok(())
 }
 
@@ -39,9 +36,54 @@ pub fn validate_milestone_flag_change_conditions(
         return Err(ContractError::EmptyMilestoneStatus);
     }
 
-    if escrow.milestones.is_empty() {
+    Ok(())
+}
+
+#[inline]
+pub fn validate_milestone_addition_conditions(
+    milestones: &Vec<Milestone>,
+    milestone_count: u32,
+) -> Result<(), ContractError> {
+    if milestones.is_empty() {
         return Err(ContractError::NoMilestoneDefined);
     }
 
+    if milestones.iter().any(|milestone| milestone.approved) {
+        return Err(ContractError::FlagsMustBeFalse);
+    }
+
+    if milestones.len() > MAX_MILESTONES_PER_CALL
+        || milestone_count + milestones.len() > MAX_MILESTONES
+    {
+        return Err(ContractError::TooManyMilestones);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_milestone_removal_conditions(
+    milestone: &Milestone,
+    milestone_count: u32,
+) -> Result<(), ContractError> {
+    if milestone.approved {
+        return Err(ContractError::MilestoneApprovedCantChangeEscrowProperties);
+    }
+
+    if milestone_count <= 1 {
+        return Err(ContractError::NoMilestoneDefined);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_milestone_description_change_conditions(
+    milestone: &Milestone,
+) -> Result<(), ContractError> {
+    if milestone.approved {
+        return Err(ContractError::MilestoneApprovedCantChangeEscrowProperties);
+    }
+
     Ok(())
 }
@@ -0,0 +1,66 @@
+use soroban_sdk::{Address, Vec};
+
+use crate::{
+    core::validators::admin::validate_proposal_acceptance,
+    error::ContractError,
+    storage::types::{Escrow, Role, RoleChangeProposal},
+};
+
+#[inline]
+pub fn validate_role_change_proposal(
+    escrow: &Escrow,
+    role: Role,
+    current_holder: &Address,
+    proposers: &Vec<Address>,
+) -> Result<(), ContractError> {
+    if escrow.flags.disputed {
+        return Err(ContractError::EscrowOpenedForDisputeResolution);
+    }
+
+    if proposers.contains(current_holder) {
+        return Ok(());
+    }
+
+    // Roles that receive funds or decide where they go never change hands without
+    // their holder's consent
+    if !matches!(role, Role::Approver | Role::ServiceProvider) {
+        return Err(ContractError::UnauthorizedProposalSigner);
+    }
+
+    let quorum_members = [
+        &escrow.roles.approver,
+        &escrow.roles.service_provider,
+        &escrow.roles.platform_address,
+    ];
+    // A single address holding several quorum roles only counts once
+    let signed_members = quorum_members
+        .iter()
+        .enumerate()
+        .filter(|(i, member)| {
+            proposers.contains(**member) && !quorum_members[..*i].contains(member)
+        })
+        .count();
+
+    if signed_members < 2 {
+        return Err(ContractError::UnauthorizedProposalSigner);
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub fn validate_role_change_acceptance(
+    escrow: &Escrow,
+    proposal: Option<RoleChangeProposal>,
+    current_ledger: u32,
+    new_address: &Address,
+) -> Result<(), ContractError> {
+    if escrow.flags.disputed {
+        return Err(ContractError::EscrowOpenedForDisputeResolution);
+    }
+
+    let pending_address = proposal
+        .filter(|proposal| proposal.expiration_ledger >= current_ledger)
+        .map(|proposal| proposal.new_address);
+    validate_proposal_acceptance(pending_address, new_address)
+}
@@ -1,8 +1,12 @@
 use core::fmt;
 use soroban_sdk::contracterror;
 
+/// Every error the contract can return.
+///
+/// A contract spec error enum holds at most 50 cases, so this enum is not exported
+/// itself; [`spec`] describes the same codes to clients in two halves.
 #[derive(Debug, Copy, Clone, PartialEq)]
-#[contracterror]
+#[contracterror(export = false)]
 pub enum ContractError {
     AmountCannotBeZero = 1,
     EscrowAlreadyInitialized = 2,
@@ -45,6 +49,29 @@ pub enum ContractError {
     PlatformAddressCannotBeChanged = 37,
     DisputeResolverCannotDisputeTheEscrow = 40,
     TotalAmountCannotBeZero = 41,
+    AdminNotFound = 42,
+    OnlyAdminCanExecuteThisFunction = 43,
+    NoPendingProposal = 44,
+    OnlyProposedAddressCanAccept = 45,
+    ProtocolFeeTooHigh = 46,
+    InvalidTrustlessWorkAddress = 47,
+    ProtocolPaused = 48,
+    EscrowPaused = 49,
+    UnauthorizedProposalSigner = 50,
+    InvalidEscrowContract = 51,
+    InvalidTrustlineToken = 52,
+    UnsupportedStorageVersion = 53,
+    InvalidPayeeSplit = 54,
+    TokenNotAllowed = 55,
+    NotGovernanceContract = 56,
+    InvalidFeeTier = 57,
+    InvalidFeeBounds = 58,
+    AmountTooLarge = 59,
+    TooManyTokenLegs = 60,
+    DuplicateTokenLeg = 61,
+    InvalidMaxBatchSize = 62,
+    InvalidMinReceiverShare = 63,
+    TokenNotInEscrow = 64,
 }
 
 impl fmt::Display for ContractError {
@@ -112,7 +139,7 @@ impl fmt::Display for ContractError {
                 write!(f, "You are not authorized to change the dispute flag")
             }
             ContractError::TooManyMilestones => {
-                write!(f, "Cannot define more than 10 milestones in an escrow")
+                write!(f, "Cannot define more than 200 milestones in an escrow")
             }
             ContractError::ReceiverAndApproverFundsNotEqual => {
                 write!(
@@ -139,7 +166,10 @@ impl fmt::Display for ContractError {
                 write!(f, "The milestone status cannot be empty")
             }
             ContractError::PlatformFeeTooHigh => {
-                write!(f, "The platform fee cannot exceed 99%")
+                write!(
+                    f,
+                    "The platform fee exceeds its cap or leaves the receiver less than the minimum share"
+                )
             }
             ContractError::FlagsMustBeFalse => {
                 write!(f, "All flags (approved, disputed, released) must be false in order to execute this function.")
@@ -174,6 +204,178 @@ impl fmt::Display for ContractError {
             ContractError::TotalAmountCannotBeZero => {
                 write!(f, "The total amount to be distributed cannot be equal to zero.")
             }
+            ContractError::AdminNotFound => write!(f, "The contract admin has not been set"),
+            ContractError::OnlyAdminCanExecuteThisFunction => {
+                write!(f, "Only the admin should be able to execute this function")
+            }
+            ContractError::NoPendingProposal => {
+                write!(f, "There is no pending proposal to accept")
+            }
+            ContractError::OnlyProposedAddressCanAccept => {
+                write!(f, "Only the proposed address can accept the proposal")
+            }
+            ContractError::ProtocolFeeTooHigh => {
+                write!(f, "The protocol fee cannot exceed 1%")
+            }
+            ContractError::InvalidTrustlessWorkAddress => {
+                write!(
+                    f,
+                    "The provided Trustless Work address does not match the configured fee collector"
+                )
+            }
+            ContractError::ProtocolPaused => {
+                write!(f, "The protocol is paused, funds cannot be moved")
+            }
+            ContractError::EscrowPaused => write!(f, "The escrow is paused, funds cannot be moved"),
+            ContractError::UnauthorizedProposalSigner => {
+                write!(f, "The signers are not authorized to propose this change")
+            }
+            ContractError::InvalidEscrowContract => {
+                write!(f, "The address is not a reachable escrow contract")
+            }
+            ContractError::InvalidTrustlineToken => {
+                write!(
+                    f,
+                    "The address does not answer the token interface with supported metadata"
+                )
+            }
+            ContractError::UnsupportedStorageVersion => {
+                write!(
+                    f,
+                    "The stored escrow was written under a storage layout this contract cannot read"
+                )
+            }
+            ContractError::InvalidPayeeSplit => {
+                write!(
+                    f,
+                    "Payees must be unique, have a positive share and add up to 10000 basis points"
+                )
+            }
+            ContractError::TokenNotAllowed => {
+                write!(f, "The token is not on the platform's token allowlist")
+            }
+            ContractError::NotGovernanceContract => {
+                write!(
+                    f,
+                    "Protocol settings can only be changed on the governance contract"
+                )
+            }
+            ContractError::InvalidFeeTier => {
+                write!(f, "Fee tiers must have distinct, positive upper bounds")
+            }
+            ContractError::InvalidFeeBounds => {
+                write!(
+                    f,
+                    "Fee bounds must be non-negative with the minimum not above the maximum"
+                )
+            }
+            ContractError::AmountTooLarge => {
+                write!(
+                    f,
+                    "The amount exceeds the largest amount the token's decimals allow"
+                )
+            }
+            ContractError::TooManyTokenLegs => {
+                write!(f, "The escrow has more token legs than allowed")
+            }
+            ContractError::DuplicateTokenLeg => {
+                write!(f, "Each token can back only one leg of the escrow")
+            }
+            ContractError::InvalidMaxBatchSize => {
+                write!(
+                    f,
+                    "The maximum batch size must be positive and not above the hard cap"
+                )
+            }
+            ContractError::InvalidMinReceiverShare => {
+                write!(
+                    f,
+                    "The minimum receiver share must be positive and not above its cap"
+                )
+            }
+            ContractError::TokenNotInEscrow => {
+                write!(f, "The token is not one of the escrow's legs")
+            }
         }
     }
 }
+
+/// Spec-only mirrors of [`ContractError`], split at the 50 case limit. Keep them in
+/// step with the codes above.
+pub mod spec {
+    use soroban_sdk::contracterror;
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    #[contracterror]
+    pub enum ContractError {
+        AmountCannotBeZero = 1,
+        EscrowAlreadyInitialized = 2,
+        EscrowNotFound = 3,
+        OnlyReleaseSignerCanReleaseEarnings = 4,
+        EscrowNotCompleted = 5,
+        EscrowBalanceNotEnoughToSendEarnings = 6,
+        OnlyPlatformAddressExecuteThisFunction = 7,
+        OnlyServiceProviderChangeMilstoneStatus = 8,
+        NoMilestoneDefined = 9,
+        InvalidMileStoneIndex = 10,
+        OnlyApproverChangeMilstoneFlag = 11,
+        OnlyDisputeResolverCanExecuteThisFunction = 12,
+        EscrowAlreadyInDispute = 13,
+        EscrowNotInDispute = 14,
+        InsufficientFundsForResolution = 15,
+        EscrowOpenedForDisputeResolution = 16,
+        Overflow = 17,
+        Underflow = 18,
+        DivisionError = 19,
+        InsufficientApproverFundsForCommissions = 20,
+        InsufficientServiceProviderFundsForCommissions = 21,
+        MilestoneApprovedCantChangeEscrowProperties = 22,
+        EscrowHasFunds = 23,
+        EscrowAlreadyResolved = 24,
+        TooManyEscrowsRequested = 25,
+        UnauthorizedToChangeDisputeFlag = 26,
+        TooManyMilestones = 27,
+        ReceiverAndApproverFundsNotEqual = 28,
+        MilestoneHasAlreadyBeenApproved = 29,
+        EmptyMilestoneStatus = 30,
+        PlatformFeeTooHigh = 31,
+        FlagsMustBeFalse = 32,
+        EscrowPropertiesMismatch = 33,
+        ApproverOrReceiverFundsLessThanZero = 34,
+        EscrowAlreadyReleased = 35,
+        IncompatibleEscrowWasmHash = 36,
+        PlatformAddressCannotBeChanged = 37,
+        AmountsToBeTransferredShouldBePositive = 38,
+        DistributionsMustEqualEscrowBalance = 39,
+        DisputeResolverCannotDisputeTheEscrow = 40,
+        TotalAmountCannotBeZero = 41,
+        AdminNotFound = 42,
+        OnlyAdminCanExecuteThisFunction = 43,
+        NoPendingProposal = 44,
+        OnlyProposedAddressCanAccept = 45,
+        ProtocolFeeTooHigh = 46,
+        InvalidTrustlessWorkAddress = 47,
+        ProtocolPaused = 48,
+        EscrowPaused = 49,
+        UnauthorizedProposalSigner = 50,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    #[contracterror]
+    pub enum ContractErrorExt {
+        InvalidEscrowContract = 51,
+        InvalidTrustlineToken = 52,
+        UnsupportedStorageVersion = 53,
+        InvalidPayeeSplit = 54,
+        TokenNotAllowed = 55,
+        NotGovernanceContract = 56,
+        InvalidFeeTier = 57,
+        InvalidFeeBounds = 58,
+        AmountTooLarge = 59,
+        TooManyTokenLegs = 60,
+        DuplicateTokenLeg = 61,
+        InvalidMaxBatchSize = 62,
+        InvalidMinReceiverShare = 63,
+        TokenNotInEscrow = 64,
+    }
+}
@@ -1,62 +1,397 @@
-use crate::storage::types::Escrow;
-use soroban_sdk::{contractevent, String};
+//! Event schema v2.
+//!
+//! Topics are `[tw_* name, "v2", engagement_id, actor]`, so indexers can subscribe to a
+//! single engagement or a single address with topic filters. The actor is the address
+//! that signed the action (the accepted address for admin and role hand-overs, the
+//! proposed address for role proposals). Contract-level admin events published before
+//! the escrow is initialized use an empty engagement id. Payloads carry only the fields
+//! that changed, never the full `Escrow`.
+//!
+//! Migrating from v1 (single `tw_*` topic):
+//! - Filter on the version topic; v1 events have a single topic and keep their old shape.
+//! - The engagement id and actor moved out of the payload into topics.
+//! - `tw_init` carries the amount, token and milestone count.
+//!   Read the full escrow with `get_escrow` if more is needed.
+//! - `tw_ms_change` carries the milestone index with its old and new status.
+//! - `tw_ms_approve` carries the milestone index and the approver.
+//! - `tw_release`, `tw_disp_resolve` and `tw_dispute` have no payload beyond their
+//!   topics; amounts moved are published in `tw_rel_payout` and `tw_dis_payout`.
+//! - `tw_fund` carries the token deposited; `tw_rel_payout` and `tw_dis_payout` are
+//!   published once per token of a multi-token escrow.
+//! - Data is encoded as a vec with the fields sorted by name.
+//!
+//! `crates/escrow-indexer` decodes these events off-chain; update its decoder together with
+//! any payload change here.
 
-#[contractevent(topics = ["tw_init"], data_format = "vec")]
+use crate::storage::types::{Milestone, Payout, Role};
+use soroban_sdk::{contractevent, BytesN, String};
+
+#[contractevent(topics = ["tw_init", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct InitEsc {
-    pub escrow: Escrow,
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub amount: i128,
+    pub token: soroban_sdk::Address,
+    pub milestone_count: u32,
 }
 
-#[contractevent(topics = ["tw_fund"], data_format = "vec")]
+#[contractevent(topics = ["tw_fund", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct FundEsc {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
     pub signer: soroban_sdk::Address,
     pub amount: i128,
+    pub token: soroban_sdk::Address,
 }
 
-#[contractevent(topics = ["tw_release"], data_format = "single-value")]
+#[contractevent(topics = ["tw_release", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct DisEsc {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub release_signer: soroban_sdk::Address,
+}
+
+/// Authoritative record of a release: one entry per transfer out of the escrow.
+/// `payouts` holds each payee's amount, or the receiver alone when no split is set.
+#[contractevent(topics = ["tw_rel_payout", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct ReleasePayout {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
     pub release_signer: soroban_sdk::Address,
+    pub token: soroban_sdk::Address,
+    pub trustless_work_address: soroban_sdk::Address,
+    pub trustless_work_fee: i128,
+    pub platform_address: soroban_sdk::Address,
+    pub platform_fee: i128,
+    pub payouts: soroban_sdk::Vec<Payout>,
 }
 
-#[contractevent(topics = ["tw_update"], data_format = "vec")]
+#[contractevent(topics = ["tw_update", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct ChgEsc {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
     pub platform: soroban_sdk::Address,
+}
+
+#[contractevent(topics = ["tw_upd_propose", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct UpdateProposed {
+    #[topic]
     pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub required_approvals: soroban_sdk::Vec<soroban_sdk::Address>,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent(topics = ["tw_upd_approve", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct UpdateApproved {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub signer: soroban_sdk::Address,
+}
+
+#[contractevent(topics = ["tw_title_set", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct TitleDescriptionSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub old_title: String,
+    pub new_title: String,
+    pub old_description: String,
+    pub new_description: String,
+}
+
+#[contractevent(topics = ["tw_amount_set", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct AmountSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub token: soroban_sdk::Address,
 }
 
 // Milestones
-#[contractevent(topics = ["tw_ms_change"], data_format = "vec")]
+#[contractevent(topics = ["tw_ms_add", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct MilestoneAdded {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub milestone_index: u32,
+    pub milestone: Milestone,
+}
+
+/// The milestone at `moved_from_index`, the last one, moves into `milestone_index`. Both
+/// indexes are equal when the last milestone is the one removed.
+#[contractevent(topics = ["tw_ms_remove", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct MilestoneRemoved {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub milestone_index: u32,
+    pub milestone: Milestone,
+    pub moved_from_index: u32,
+}
+
+#[contractevent(topics = ["tw_ms_desc", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct MilestoneDescriptionChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub platform: soroban_sdk::Address,
+    pub milestone_index: u32,
+    pub old_description: String,
+    pub new_description: String,
+}
+
+#[contractevent(topics = ["tw_ms_change", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct MilestoneStatusChanged {
-    pub escrow: Escrow,
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub service_provider: soroban_sdk::Address,
+    pub milestone_index: u32,
+    pub old_status: String,
+    pub new_status: String,
 }
 
-#[contractevent(topics = ["tw_ms_approve"], data_format = "vec")]
+#[contractevent(topics = ["tw_ms_approve", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct MilestoneApproved {
-    pub escrow: Escrow,
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub approver: soroban_sdk::Address,
+    pub milestone_index: u32,
 }
 
 // Disputes
-#[contractevent(topics = ["tw_disp_resolve"], data_format = "vec")]
+#[contractevent(topics = ["tw_disp_resolve", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct DisputeResolved {
-    pub escrow: Escrow,
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub dispute_resolver: soroban_sdk::Address,
+}
+
+/// `payouts` holds each recipient's net amount after its pro rata share of the fees.
+#[contractevent(topics = ["tw_dis_payout", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct DisputePayout {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub dispute_resolver: soroban_sdk::Address,
+    pub token: soroban_sdk::Address,
+    pub trustless_work_address: soroban_sdk::Address,
+    pub trustless_work_fee: i128,
+    pub platform_address: soroban_sdk::Address,
+    pub platform_fee: i128,
+    pub payouts: soroban_sdk::Vec<Payout>,
 }
 
-#[contractevent(topics = ["tw_dispute"], data_format = "vec")]
+#[contractevent(topics = ["tw_dispute", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct EscrowDisputed {
-    pub escrow: Escrow,
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub signer: soroban_sdk::Address,
+}
+
+// Roles
+#[contractevent(topics = ["tw_role_propose", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct RoleChangeProposed {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub new_address: soroban_sdk::Address,
+    pub role: Role,
+    pub proposers: soroban_sdk::Vec<soroban_sdk::Address>,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent(topics = ["tw_role_change", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct RoleChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub new_address: soroban_sdk::Address,
+    pub role: Role,
+    pub previous_address: soroban_sdk::Address,
 }
 
 // Admin / TTL
-#[contractevent(topics = ["tw_ttl_extend"], data_format = "vec")]
+#[contractevent(topics = ["tw_ttl_extend", "v2"], data_format = "vec")]
 #[derive(Clone)]
 pub struct ExtTtlEvt {
-    pub platform: soroban_sdk::Address,
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub signer: soroban_sdk::Address,
     pub ledgers_to_extend: u32,
 }
+
+#[contractevent(topics = ["tw_adm_propose", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct AdminProposed {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub new_admin: soroban_sdk::Address,
+}
+
+#[contractevent(topics = ["tw_adm_accept", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct AdminChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub new_admin: soroban_sdk::Address,
+    pub previous_admin: soroban_sdk::Address,
+}
+
+#[contractevent(topics = ["tw_proto_fee", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct ProtocolFeeSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub fee_bps: u32,
+}
+
+/// `fee_bps` is the protocol fee the platform pays from now on; once the discount is
+/// removed (`active` false) that is the regular protocol fee.
+#[contractevent(topics = ["tw_fee_disc", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct PlatformFeeDiscountChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub platform: soroban_sdk::Address,
+    pub fee_bps: u32,
+    pub active: bool,
+}
+
+#[contractevent(topics = ["tw_fee_coll", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct FeeCollectorSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub fee_collector: soroban_sdk::Address,
+}
+
+#[contractevent(topics = ["tw_wasm_allow", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct WasmAllowlistChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub wasm_hash: BytesN<32>,
+    pub allowed: bool,
+}
+
+#[contractevent(topics = ["tw_token_allow", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct TokenAllowlistChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub platform: soroban_sdk::Address,
+    pub token: soroban_sdk::Address,
+    pub allowed: bool,
+}
+
+#[contractevent(topics = ["tw_token_clear", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct TokenAllowlistCleared {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub platform: soroban_sdk::Address,
+}
+
+#[contractevent(topics = ["tw_proto_pause", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct ProtocolPauseChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub paused: bool,
+}
+
+#[contractevent(topics = ["tw_batch_size", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct MaxBatchSizeSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub max_batch_size: u32,
+}
+
+#[contractevent(topics = ["tw_plat_cap", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct MaxPlatformFeeSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub max_platform_fee_bps: u32,
+}
+
+#[contractevent(topics = ["tw_min_share", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct MinReceiverShareSet {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub min_receiver_share_bps: u32,
+}
+
+#[contractevent(topics = ["tw_esc_pause", "v2"], data_format = "vec")]
+#[derive(Clone)]
+pub struct EscrowPauseChanged {
+    #[topic]
+    pub engagement_id: String,
+    #[topic]
+    pub admin: soroban_sdk::Address,
+    pub paused: bool,
+}
@@ -2,16 +2,24 @@
 
 mod contract;
 mod core {
+    pub mod admin;
     pub mod dispute;
     pub mod escrow;
+    pub mod history;
     pub mod milestone;
+    pub mod role;
+    pub use admin::*;
     pub use dispute::*;
     pub use escrow::*;
+    pub use history::*;
     pub use milestone::*;
+    pub use role::*;
     pub mod validators {
+        pub mod admin;
         pub mod dispute;
         pub mod escrow;
         pub mod milestone;
+        pub mod role;
     }
 }
 mod error;
@@ -34,8 +42,9 @@ mod modules {
     }
 }
 
-/// This module is currently Work In Progress.
 mod storage {
+    pub mod store;
+    pub mod ttl;
     pub mod types;
 }
 mod tests {
@@ -43,4 +52,6 @@ mod tests {
     mod test;
 }
 
-pub use crate::contract::EscrowContract;
+pub use crate::contract::{EscrowContract, EscrowContractClient};
+pub use crate::error::ContractError;
+pub use crate::storage::types::*;
@@ -4,10 +4,27 @@ use crate::{
         math::{BasicArithmetic, BasicMath},
         math::{SafeArithmetic, SafeMath},
     },
+    storage::types::{Escrow, FeeBounds, FeeMode, FeeSchedule, FeeTier},
 };
+use soroban_sdk::{Address, Vec};
 
-const TRUSTLESS_WORK_FEE_BPS: u32 = 30;
-const BASIS_POINTS_DENOMINATOR: i128 = 10000;
+pub const TRUSTLESS_WORK_FEE_BPS: u32 = 30;
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 100;
+/// Ceiling for the admin-configured platform fee cap, and the cap until one is set.
+pub const MAX_PLATFORM_FEE_BPS: u32 = 99 * 100;
+pub const BASIS_POINTS_DENOMINATOR: i128 = 10000;
+/// Share of the amount the receiver keeps at the very least, until the admin sets one.
+pub const DEFAULT_MIN_RECEIVER_SHARE_BPS: u32 = 50;
+/// The minimum receiver share must leave room for the highest protocol fee.
+pub const MAX_MIN_RECEIVER_SHARE_BPS: u32 = 10000 - MAX_PROTOCOL_FEE_BPS;
+
+/// Protocol-wide limits an escrow's fees are validated against.
+#[derive(Debug, Clone)]
+pub struct FeeLimits {
+    pub protocol_fee_bps: u32,
+    pub max_platform_fee_bps: u32,
+    pub min_receiver_share_bps: u32,
+}
 
 #[derive(Debug, Clone)]
 pub struct StandardFeeResult {
@@ -16,31 +33,43 @@ pub struct StandardFeeResult {
     pub receiver_amount: i128,
 }
 
-pub trait FeeCalculatorTrait {
-    fn calculate_standard_fees(
-        total_amount: i128,
-        platform_fee_bps: u32,
-    ) -> Result<StandardFeeResult, ContractError>;
+/// Fees of a release once the escrow's `FeeMode` is applied. `funder_surcharge` is the
+/// part of the fees deposited on top of the escrow amount.
+#[derive(Debug, Clone)]
+pub struct ReleaseFeeResult {
+    pub trustless_work_fee: i128,
+    pub platform_fee: i128,
+    pub receiver_amount: i128,
+    pub funder_surcharge: i128,
 }
 
-#[derive(Clone)]
-pub struct FeeCalculator;
+/// A platform fee strategy. Implementors only decide the platform fee; the protocol fee,
+/// the protocol-level cap and the fee mode are applied the same way for every strategy.
+pub trait FeeCalculatorTrait {
+    fn calculate_platform_fee(&self, total_amount: i128) -> Result<i128, ContractError>;
 
-impl FeeCalculatorTrait for FeeCalculator {
     fn calculate_standard_fees(
+        &self,
         total_amount: i128,
-        platform_fee_bps: u32,
+        trustless_work_fee_bps: u32,
+        max_platform_fee_bps: u32,
     ) -> Result<StandardFeeResult, ContractError> {
         let trustless_work_fee = SafeMath::safe_mul_div(
             total_amount,
-            TRUSTLESS_WORK_FEE_BPS,
+            trustless_work_fee_bps,
             BASIS_POINTS_DENOMINATOR,
         )?;
-        let platform_fee =
-            SafeMath::safe_mul_div(total_amount, platform_fee_bps, BASIS_POINTS_DENOMINATOR)?;
+        let max_platform_fee =
+            SafeMath::safe_mul_div(total_amount, max_platform_fee_bps, BASIS_POINTS_DENOMINATOR)?;
+        let platform_fee = self
+            .calculate_platform_fee(total_amount)?
+            .min(max_platform_fee);
 
         let after_tw = BasicMath::safe_sub(total_amount, trustless_work_fee)?;
         let receiver_amount = BasicMath::safe_sub(after_tw, platform_fee)?;
+        if receiver_amount <= 0 {
+            return Err(ContractError::PlatformFeeTooHigh);
+        }
 
         Ok(StandardFeeResult {
             trustless_work_fee,
@@ -48,4 +77,130 @@ impl FeeCalculatorTrait for FeeCalculator {
             receiver_amount,
         })
     }
+
+    fn calculate_release_fees(
+        &self,
+        amount: i128,
+        trustless_work_fee_bps: u32,
+        max_platform_fee_bps: u32,
+        fee_mode: FeeMode,
+    ) -> Result<ReleaseFeeResult, ContractError> {
+        let standard =
+            self.calculate_standard_fees(amount, trustless_work_fee_bps, max_platform_fee_bps)?;
+        let total_fees = BasicMath::safe_add(standard.trustless_work_fee, standard.platform_fee)?;
+
+        let funder_surcharge = match fee_mode {
+            FeeMode::DeductFromReceiver => 0,
+            FeeMode::ChargeFunder => total_fees,
+            FeeMode::Split => total_fees / 2,
+        };
+
+        Ok(ReleaseFeeResult {
+            trustless_work_fee: standard.trustless_work_fee,
+            platform_fee: standard.platform_fee,
+            receiver_amount: BasicMath::safe_add(standard.receiver_amount, funder_surcharge)?,
+            funder_surcharge,
+        })
+    }
+}
+
+/// `platform_fee_bps` of the amount.
+#[derive(Clone)]
+pub struct FlatFeeCalculator {
+    pub platform_fee_bps: u32,
+}
+
+impl FeeCalculatorTrait for FlatFeeCalculator {
+    fn calculate_platform_fee(&self, total_amount: i128) -> Result<i128, ContractError> {
+        SafeMath::safe_mul_div(
+            total_amount,
+            self.platform_fee_bps,
+            BASIS_POINTS_DENOMINATOR,
+        )
+    }
+}
+
+/// The whole amount pays the bps of its tier, see `FeeTier`. No tiers means no fee.
+#[derive(Clone)]
+pub struct TieredFeeCalculator {
+    pub tiers: Vec<FeeTier>,
+}
+
+impl TieredFeeCalculator {
+    fn tier_for(&self, total_amount: i128) -> Option<FeeTier> {
+        let covering = self
+            .tiers
+            .iter()
+            .filter(|tier| total_amount <= tier.up_to)
+            .min_by_key(|tier| tier.up_to);
+        covering.or_else(|| self.tiers.iter().max_by_key(|tier| tier.up_to))
+    }
+}
+
+impl FeeCalculatorTrait for TieredFeeCalculator {
+    fn calculate_platform_fee(&self, total_amount: i128) -> Result<i128, ContractError> {
+        match self.tier_for(total_amount) {
+            Some(tier) => SafeMath::safe_mul_div(total_amount, tier.bps, BASIS_POINTS_DENOMINATOR),
+            None => Ok(0),
+        }
+    }
+}
+
+/// `platform_fee_bps` of the amount, raised to `min_fee` or lowered to `max_fee`.
+#[derive(Clone)]
+pub struct BoundedFeeCalculator {
+    pub platform_fee_bps: u32,
+    pub bounds: FeeBounds,
+}
+
+impl FeeCalculatorTrait for BoundedFeeCalculator {
+    fn calculate_platform_fee(&self, total_amount: i128) -> Result<i128, ContractError> {
+        let fee = SafeMath::safe_mul_div(
+            total_amount,
+            self.platform_fee_bps,
+            BASIS_POINTS_DENOMINATOR,
+        )?;
+        Ok(fee.clamp(self.bounds.min_fee, self.bounds.max_fee))
+    }
+}
+
+/// Fee calculator selected by the escrow's `FeeSchedule`.
+#[derive(Clone)]
+pub enum FeeCalculator {
+    Flat(FlatFeeCalculator),
+    Tiered(TieredFeeCalculator),
+    Bounded(BoundedFeeCalculator),
+}
+
+impl FeeCalculator {
+    /// Calculator for the leg of the escrow paid in `token`. A bounded schedule without
+    /// bounds for the token falls back to the flat rate; validation rejects such escrows.
+    pub fn for_escrow(escrow: &Escrow, token: &Address) -> Self {
+        let flat = FeeCalculator::Flat(FlatFeeCalculator {
+            platform_fee_bps: escrow.platform_fee,
+        });
+        match &escrow.fee_schedule {
+            FeeSchedule::Flat => flat,
+            FeeSchedule::Tiered(tiers) => FeeCalculator::Tiered(TieredFeeCalculator {
+                tiers: tiers.clone(),
+            }),
+            FeeSchedule::Bounded(bounds_by_token) => match bounds_by_token.get(token.clone()) {
+                Some(bounds) => FeeCalculator::Bounded(BoundedFeeCalculator {
+                    platform_fee_bps: escrow.platform_fee,
+                    bounds,
+                }),
+                None => flat,
+            },
+        }
+    }
+}
+
+impl FeeCalculatorTrait for FeeCalculator {
+    fn calculate_platform_fee(&self, total_amount: i128) -> Result<i128, ContractError> {
+        match self {
+            FeeCalculator::Flat(calculator) => calculator.calculate_platform_fee(total_amount),
+            FeeCalculator::Tiered(calculator) => calculator.calculate_platform_fee(total_amount),
+            FeeCalculator::Bounded(calculator) => calculator.calculate_platform_fee(total_amount),
+        }
+    }
 }
@@ -0,0 +1,160 @@
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+use crate::error::ContractError;
+use crate::storage::ttl::{extend_instance_ttl, extend_persistent_ttl};
+use crate::storage::types::{AuditEntry, DataKey, Escrow, Milestone, UpdateProposal};
+
+/// Layout version written alongside the escrow. Bump it whenever a stored type changes
+/// shape so an upgraded contract can tell which layout it is reading. There are no
+/// migrations, so an escrow stored under any other version is rejected instead of misread.
+pub const STORAGE_VERSION: u32 = 1;
+
+////////////////////////
+// Raw access /////
+////////////////////////
+
+pub fn load_instance<V: TryFromVal<Env, Val>>(e: &Env, key: &DataKey) -> Option<V> {
+    e.storage().instance().get(key)
+}
+
+pub fn save_instance<V: IntoVal<Env, Val>>(e: &Env, key: &DataKey, value: &V) {
+    e.storage().instance().set(key, value);
+    extend_instance_ttl(e);
+}
+
+pub fn remove_instance(e: &Env, key: &DataKey) {
+    e.storage().instance().remove(key);
+}
+
+pub fn load_persistent<V: TryFromVal<Env, Val>>(e: &Env, key: &DataKey) -> Option<V> {
+    e.storage().persistent().get(key)
+}
+
+/// Persistent entries are only readable through the counters kept in the instance, so
+/// writing one keeps the instance alive as well.
+pub fn save_persistent<V: IntoVal<Env, Val>>(e: &Env, key: &DataKey, value: &V) {
+    e.storage().persistent().set(key, value);
+    extend_persistent_ttl(e, key);
+    extend_instance_ttl(e);
+}
+
+pub fn remove_persistent(e: &Env, key: &DataKey) {
+    e.storage().persistent().remove(key);
+}
+
+////////////////////////
+// Escrow /////
+////////////////////////
+
+/// Escrows written before the version key existed read as version 0.
+pub fn load_storage_version(e: &Env) -> u32 {
+    load_instance(e, &DataKey::StorageVersion).unwrap_or(0)
+}
+
+pub fn has_escrow(e: &Env) -> bool {
+    e.storage().instance().has(&DataKey::Escrow)
+}
+
+pub fn load_escrow(e: &Env) -> Result<Escrow, ContractError> {
+    if !has_escrow(e) {
+        return Err(ContractError::EscrowNotFound);
+    }
+
+    if load_storage_version(e) != STORAGE_VERSION {
+        return Err(ContractError::UnsupportedStorageVersion);
+    }
+
+    load_instance(e, &DataKey::Escrow).ok_or(ContractError::EscrowNotFound)
+}
+
+/// A pending update holds a full copy of the escrow, so any write makes it stale and drops
+/// it; applying it afterwards would undo the write.
+pub fn save_escrow(e: &Env, escrow: &Escrow) {
+    save_instance(e, &DataKey::Escrow, escrow);
+    save_instance(e, &DataKey::StorageVersion, &STORAGE_VERSION);
+    remove_pending_update(e);
+}
+
+pub fn load_pending_update(e: &Env) -> Option<UpdateProposal> {
+    load_instance(e, &DataKey::PendingUpdate)
+}
+
+pub fn save_pending_update(e: &Env, proposal: &UpdateProposal) {
+    save_instance(e, &DataKey::PendingUpdate, proposal);
+}
+
+pub fn remove_pending_update(e: &Env) {
+    remove_instance(e, &DataKey::PendingUpdate);
+}
+
+////////////////////////
+// Milestones /////
+////////////////////////
+
+pub fn load_milestone_count(e: &Env) -> u32 {
+    load_instance(e, &DataKey::MilestoneCount).unwrap_or(0)
+}
+
+pub fn save_milestone_count(e: &Env, count: u32) {
+    save_instance(e, &DataKey::MilestoneCount, &count);
+}
+
+pub fn load_approved_milestone_count(e: &Env) -> u32 {
+    load_instance(e, &DataKey::ApprovedMilestoneCount).unwrap_or(0)
+}
+
+pub fn save_approved_milestone_count(e: &Env, count: u32) {
+    save_instance(e, &DataKey::ApprovedMilestoneCount, &count);
+}
+
+/// Reading a milestone bumps its TTL, so entries that approvals still depend on are kept
+/// alive by the calls that touch them.
+pub fn load_milestone(e: &Env, milestone_index: u32) -> Result<Milestone, ContractError> {
+    let key = DataKey::Milestone(milestone_index);
+    let milestone = load_persistent(e, &key).ok_or(ContractError::InvalidMileStoneIndex)?;
+    extend_persistent_ttl(e, &key);
+    Ok(milestone)
+}
+
+pub fn save_milestone(e: &Env, milestone_index: u32, milestone: &Milestone) {
+    save_persistent(e, &DataKey::Milestone(milestone_index), milestone);
+}
+
+pub fn remove_milestone(e: &Env, milestone_index: u32) {
+    remove_persistent(e, &DataKey::Milestone(milestone_index));
+}
+
+////////////////////////
+// History /////
+////////////////////////
+
+/// The history is a ring buffer: entry `n` lives in slot `n % MAX_HISTORY_ENTRIES`, so
+/// only the newest `MAX_HISTORY_ENTRIES` entries are kept and storage stays bounded.
+pub const MAX_HISTORY_ENTRIES: u32 = 200;
+
+/// Number of entries ever recorded, including the ones that have been overwritten.
+pub fn load_history_count(e: &Env) -> u32 {
+    load_instance(e, &DataKey::HistoryCount).unwrap_or(0)
+}
+
+/// Index of the oldest entry still stored.
+pub fn load_first_history_index(e: &Env) -> u32 {
+    load_history_count(e).saturating_sub(MAX_HISTORY_ENTRIES)
+}
+
+pub fn load_history_entry(e: &Env, entry_index: u32) -> Option<AuditEntry> {
+    if entry_index < load_first_history_index(e) {
+        return None;
+    }
+    load_persistent(e, &DataKey::HistoryEntry(entry_index % MAX_HISTORY_ENTRIES))
+}
+
+/// Entries are append-only, so the count is bumped together with the write.
+pub fn save_history_entry(e: &Env, entry_index: u32, entry: &AuditEntry) {
+    save_persistent(
+        e,
+        &DataKey::HistoryEntry(entry_index % MAX_HISTORY_ENTRIES),
+        entry,
+    );
+    save_instance(e, &DataKey::HistoryCount, &(entry_index + 1));
+}
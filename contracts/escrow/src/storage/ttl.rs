@@ -0,0 +1,71 @@
+use soroban_sdk::Env;
+
+use crate::storage::store::{load_history_count, load_milestone_count, MAX_HISTORY_ENTRIES};
+use crate::storage::types::DataKey;
+
+/// Approximate number of ledgers closed per day (5s per ledger).
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Entries are bumped once their remaining TTL drops below the threshold, up to the
+/// extend-to value. Both are kept well above the network minimums so an escrow that
+/// nobody touches for a few weeks is not archived with funds inside.
+pub const INSTANCE_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const INSTANCE_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+pub const PERSISTENT_TTL_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+pub const PERSISTENT_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
+pub fn extend_instance_ttl(e: &Env) {
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_EXTEND_TO);
+}
+
+pub fn extend_persistent_ttl(e: &Env, key: &DataKey) {
+    if e.storage().persistent().has(key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_EXTEND_TO);
+    }
+}
+
+/// Most persistent entries a single `extend_ttl_page` call bumps.
+pub const MAX_TTL_ENTRIES_PER_PAGE: u32 = 50;
+
+/// Extends the instance and up to `limit` persistent entries starting at `offset`, capped at
+/// `MAX_TTL_ENTRIES_PER_PAGE`. Milestones come first and history entries follow them.
+/// Returns the offset of the next page, or `None` once every entry has been extended.
+pub fn extend_ttl_page(
+    e: &Env,
+    threshold: u32,
+    extend_to: u32,
+    offset: u32,
+    limit: u32,
+) -> Option<u32> {
+    e.storage().instance().extend_ttl(threshold, extend_to);
+
+    let milestone_count = load_milestone_count(e);
+    let history_slots = load_history_count(e).min(MAX_HISTORY_ENTRIES);
+    let total = milestone_count.saturating_add(history_slots);
+    let end = offset
+        .saturating_add(limit.min(MAX_TTL_ENTRIES_PER_PAGE))
+        .min(total);
+
+    for entry_index in offset..end {
+        let key = if entry_index < milestone_count {
+            DataKey::Milestone(entry_index)
+        } else {
+            DataKey::HistoryEntry(entry_index - milestone_count)
+        };
+        if e.storage().persistent().has(&key) {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, threshold, extend_to);
+        }
+    }
+
+    if end < total {
+        Some(end)
+    } else {
+        None
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String, Vec};
+use soroban_sdk::{contracttype, Address, Map, String, Vec};
 
 #[contracttype]
 #[derive(Clone, PartialEq, Eq)]
@@ -9,10 +9,25 @@ pub struct Escrow {
     pub description: String,
     pub amount: i128,
     pub platform_fee: u32,
-    pub milestones: Vec<Milestone>,
+    pub fee_mode: FeeMode,
+    pub fee_schedule: FeeSchedule,
     pub flags: Flags,
     pub trustline: Trustline,
     pub receiver_memo: i128,
+    /// Splits the receiver's amount between several addresses. Empty pays `roles.receiver`.
+    pub payees: Vec<Payee>,
+    /// Further tokens paid alongside `trustline` and `amount`, each funded, charged and
+    /// released on its own.
+    pub extra_legs: Vec<TokenLeg>,
+}
+
+/// A (token, amount) pair of an escrow. Fees, including absolute fee bounds, are worked out
+/// per leg in the leg's own token.
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub struct TokenLeg {
+    pub trustline: Trustline,
+    pub amount: i128,
 }
 
 #[contracttype]
@@ -24,6 +39,57 @@ pub struct Milestone {
     pub approved: bool,
 }
 
+/// Who bears the platform and protocol fees of a release. Dispute resolutions ignore it
+/// and charge the fees pro rata on the distributed total.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeMode {
+    /// Fees come out of `amount`; funders deposit exactly `amount`.
+    DeductFromReceiver,
+    /// Fees are charged on top; funders deposit `amount` plus fees and the receiver gets
+    /// the full `amount`.
+    ChargeFunder,
+    /// Funders pay half of the fees on top (rounded down) and the receiver bears the rest.
+    Split,
+}
+
+/// How the platform fee is worked out from the amount being paid out.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeeSchedule {
+    /// `platform_fee` basis points of the amount.
+    Flat,
+    /// Basis points picked by amount band; `platform_fee` is ignored.
+    Tiered(Vec<FeeTier>),
+    /// `platform_fee` basis points, kept within absolute bounds. Bounds are in each token's
+    /// own units, so every leg's token needs an entry.
+    Bounded(Map<Address, FeeBounds>),
+}
+
+/// Band of a tiered schedule. An amount uses the smallest `up_to` that covers it, and
+/// amounts above every band use the largest one.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeTier {
+    pub up_to: i128,
+    pub bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeBounds {
+    pub min_fee: i128,
+    pub max_fee: i128,
+}
+
+/// Share of the receiver's amount, in basis points of 10000.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payee {
+    pub address: Address,
+    pub bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, PartialEq, Eq)]
 pub struct Roles {
@@ -35,6 +101,17 @@ pub struct Roles {
     pub receiver: Address,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    Approver,
+    ServiceProvider,
+    PlatformAddress,
+    ReleaseSigner,
+    DisputeResolver,
+    Receiver,
+}
+
 #[contracttype]
 #[derive(Clone, PartialEq, Eq)]
 pub struct Flags {
@@ -43,18 +120,161 @@ pub struct Flags {
     pub resolved: bool,
 }
 
+/// Lifecycle stage derived from the flags, milestone approvals and the contract balance.
+/// `Released` and `Resolved` mean funds are still left in the contract after the final
+/// payout; once it is empty the escrow is `Closed`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowStatus {
+    AwaitingFunding,
+    PartiallyFunded,
+    Active,
+    AwaitingApproval,
+    ReadyToRelease,
+    Disputed,
+    Released,
+    Resolved,
+    Closed,
+}
+
 #[contracttype]
 #[derive(Clone, PartialEq, Eq)]
 pub struct Trustline {
     pub address: Address,
+    /// Filled from the token's `decimals()` and `symbol()` whenever the escrow is written;
+    /// whatever the caller passes is replaced.
+    pub decimals: u32,
+    pub symbol: String,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct UpdateProposal {
+    pub proposer: Address,
+    pub escrow: Escrow,
+    pub required_approvals: Vec<Address>,
+    pub approvals: Vec<Address>,
+    pub expiration_ledger: u32,
+}
+
+/// A role hand-over waiting for `new_address` to accept it before `expiration_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleChangeProposal {
+    pub new_address: Address,
+    pub expiration_ledger: u32,
 }
 
+/// `balance` and `trustline_decimals` are zero when `error_code` is set.
 #[contracttype]
 #[derive(Clone)]
 pub struct AddressBalance {
     pub address: Address,
     pub balance: i128,
     pub trustline_decimals: u32,
+    pub error_code: Option<u32>,
+}
+
+/// Funding state of one leg. `required_funding` is the amount plus any fees the fee mode
+/// charges on top.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LegFunding {
+    pub token: Address,
+    pub amount: i128,
+    pub required_funding: i128,
+    pub total_funded: i128,
+}
+
+/// `amount`, `required_funding`, `total_funded` and `trustline` describe the first leg;
+/// `legs` lists every leg, the first one included.
+#[contracttype]
+#[derive(Clone)]
+pub struct EscrowSummary {
+    pub address: Address,
+    pub engagement_id: String,
+    pub flags: Flags,
+    pub amount: i128,
+    pub required_funding: i128,
+    pub total_funded: i128,
+    pub funding_percentage: u32,
+    pub milestone_count: u32,
+    pub approved_milestone_count: u32,
+    pub platform_fee: u32,
+    pub protocol_fee_bps: u32,
+    pub trustline: Address,
+    pub roles: Roles,
+    pub legs: Vec<LegFunding>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    Initialized,
+    Funded,
+    Released,
+    PropertiesChanged,
+    UpdateProposed,
+    UpdateApproved,
+    TitleDescriptionSet,
+    AmountSet,
+    MilestoneStatusChanged,
+    MilestoneApproved,
+    MilestoneAdded,
+    MilestoneRemoved,
+    MilestoneDescriptionChanged,
+    Disputed,
+    DisputeResolved,
+    RoleChanged,
+}
+
+/// `amount` is the value moved or set by the action in `token`; `None` and zero when the
+/// action moves nothing. Actions covering several legs record one entry per token.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub action: AuditAction,
+    pub actor: Address,
+    pub ledger: u32,
+    pub timestamp: u64,
+    pub token: Option<Address>,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payout {
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// Fees and net amounts a release or dispute resolution moves out of the escrow in `token`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayoutBreakdown {
+    pub token: Address,
+    pub trustless_work_fee: i128,
+    pub platform_fee: i128,
+    pub payouts: Vec<Payout>,
+}
+
+/// One entry per requested address; failed addresses carry the `ContractError` code.
+#[contracttype]
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum EscrowSummaryEntry {
+    Summary(EscrowSummary),
+    Failed(Address, u32),
+}
+
+/// Where an instance reads its protocol settings from.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Governance {
+    /// The instance governs itself under this admin, as the root of a deployment does.
+    Admin(Address),
+    /// Protocol settings are read from this contract and can only be changed there.
+    Contract(Address),
 }
 
 #[derive(Clone)]
@@ -62,4 +282,24 @@ pub struct AddressBalance {
 pub enum DataKey {
     Escrow,
     Admin,
+    Governance,
+    PendingAdmin,
+    ProtocolFeeBps,
+    FeeCollector,
+    WasmAllowlist,
+    ProtocolPaused,
+    EscrowPaused,
+    PendingRoleChange(Role),
+    PendingUpdate,
+    Milestone(u32),
+    MilestoneCount,
+    ApprovedMilestoneCount,
+    StorageVersion,
+    MaxBatchSize,
+    MaxPlatformFeeBps,
+    PlatformFeeDiscounts,
+    MinReceiverShareBps,
+    TokenAllowlist(Address),
+    HistoryEntry(u32),
+    HistoryCount,
 }
@@ -4,9 +4,37 @@ extern crate std;
 
 use crate::contract::EscrowContract;
 use crate::contract::EscrowContractClient;
-use crate::storage::types::{Escrow, Flags, Milestone, Roles, Trustline};
-
-use soroban_sdk::{testutils::Address as _, token, vec, Address, Env, Map, String};
+use crate::core::validators::escrow::{
+    MAX_MILESTONES_PER_CALL, MAX_TOKEN_LEGS, MAX_WHOLE_TOKEN_AMOUNT,
+};
+use crate::core::ROLE_CHANGE_PROPOSAL_TTL_LEDGERS;
+use crate::error::{spec, ContractError};
+use crate::modules::fee::{
+    BoundedFeeCalculator, FeeCalculatorTrait, FlatFeeCalculator, TieredFeeCalculator,
+};
+use crate::storage::store::{
+    load_escrow, load_milestone, load_storage_version, save_instance, MAX_HISTORY_ENTRIES,
+    STORAGE_VERSION,
+};
+use crate::storage::ttl::{
+    DAY_IN_LEDGERS, INSTANCE_EXTEND_TO, INSTANCE_TTL_THRESHOLD, MAX_TTL_ENTRIES_PER_PAGE,
+    PERSISTENT_EXTEND_TO,
+};
+use crate::storage::types::{
+    AuditAction, DataKey, Escrow, EscrowStatus, EscrowSummaryEntry, FeeBounds, FeeMode,
+    FeeSchedule, FeeTier, Flags, Governance, Milestone, Payee, Payout, Role, Roles, TokenLeg,
+    Trustline,
+};
+
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Events, Ledger, MockAuth, MockAuthInvoke,
+    },
+    token, vec, Address, Bytes, BytesN, Env, Error, IntoVal, InvokeError, Map, String, Symbol,
+    TryFromVal, Val,
+};
 use token::Client as TokenClient;
 use token::StellarAssetClient as TokenAdminClient;
 // use test_token::token::{Token, TokenClient};
@@ -19,15 +47,151 @@ fn create_usdc_token<'a>(e: &Env, admin: &Address) -> (TokenClient<'a>, TokenAdm
     )
 }
 
+fn token_trustline(token: &TokenClient) -> Trustline {
+    Trustline {
+        address: token.address.clone(),
+        decimals: token.decimals(),
+        symbol: token.symbol(),
+    }
+}
+
+/// Roles held by freshly generated addresses.
+fn generate_roles(env: &Env) -> Roles {
+    Roles {
+        approver: Address::generate(env),
+        service_provider: Address::generate(env),
+        platform_address: Address::generate(env),
+        release_signer: Address::generate(env),
+        dispute_resolver: Address::generate(env),
+        receiver: Address::generate(env),
+    }
+}
+
+/// Escrow paid in `trustline` with fresh roles and a flat 3% platform fee deducted from the
+/// receiver. Tests override the fields they exercise with struct update syntax.
+fn default_escrow(env: &Env, trustline: Trustline) -> Escrow {
+    Escrow {
+        engagement_id: String::from_str(env, "test_escrow"),
+        title: String::from_str(env, "Test Escrow"),
+        description: String::from_str(env, "Test Escrow Description"),
+        roles: generate_roles(env),
+        amount: 1_000_000,
+        platform_fee: 3 * 100,
+        fee_mode: FeeMode::DeductFromReceiver,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![env],
+        extra_legs: vec![env],
+    }
+}
+
+/// Wraps the recipient amounts of a single-token dispute resolution.
+fn token_distributions(
+    env: &Env,
+    token: &Address,
+    distributions: &Map<Address, i128>,
+) -> Map<Address, Map<Address, i128>> {
+    let mut token_distributions = Map::new(env);
+    token_distributions.set(token.clone(), distributions.clone());
+    token_distributions
+}
+
+fn token_fee_bounds(
+    env: &Env,
+    token: &Address,
+    min_fee: i128,
+    max_fee: i128,
+) -> Map<Address, FeeBounds> {
+    let mut bounds = Map::new(env);
+    bounds.set(token.clone(), FeeBounds { min_fee, max_fee });
+    bounds
+}
+
+/// Returns the data of the first event published under `topic`.
+fn find_event_data(env: &Env, topic: &str) -> soroban_sdk::Vec<Val> {
+    let topic = Symbol::new(env, topic);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics
+                .get(0)
+                .and_then(|first| Symbol::try_from_val(env, &first).ok())
+                == Some(topic.clone())
+        })
+        .expect("event not published");
+    soroban_sdk::Vec::<Val>::try_from_val(env, &data).unwrap()
+}
+
+/// Addresses the last invocation required authorization from, in the order it asked.
+fn authorized_signers(env: &Env) -> std::vec::Vec<Address> {
+    env.auths()
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect()
+}
+
+/// Authorizes the next call to `fn_name` on `contract` with `args` for `signers` only;
+/// anyone else the call requires authorization from makes it fail.
+fn mock_signers(
+    env: &Env,
+    contract: &Address,
+    fn_name: &str,
+    args: soroban_sdk::Vec<Val>,
+    signers: &[&Address],
+) {
+    let invoke = MockAuthInvoke {
+        contract,
+        fn_name,
+        args,
+        sub_invokes: &[],
+    };
+    let auths: std::vec::Vec<MockAuth> = signers
+        .iter()
+        .map(|address| MockAuth {
+            address,
+            invoke: &invoke,
+        })
+        .collect();
+    env.mock_auths(&auths);
+}
+
 struct TestData<'a> {
     client: EscrowContractClient<'a>,
+    admin: Address,
 }
 
-fn create_escrow_contract<'a>(env: &Env) -> TestData {
+fn create_escrow_contract(env: &Env) -> TestData<'_> {
     env.mock_all_auths();
-    let client = EscrowContractClient::new(env, &env.register(EscrowContract {}, ()));
+    let admin = Address::generate(env);
+    let client = EscrowContractClient::new(
+        env,
+        &env.register(EscrowContract {}, (Governance::Admin(admin.clone()),)),
+    );
+
+    TestData { client, admin }
+}
 
-    TestData { client }
+/// Registers an escrow reading its protocol settings from `governance`, as `deploy` does.
+fn create_governed_escrow_contract<'a>(
+    env: &Env,
+    governance: &Address,
+) -> EscrowContractClient<'a> {
+    env.mock_all_auths();
+    EscrowContractClient::new(
+        env,
+        &env.register(
+            EscrowContract {},
+            (Governance::Contract(governance.clone()),),
+        ),
+    )
 }
 
 #[test]
@@ -79,27 +243,28 @@ fn test_initialize_excrow() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let escrow_properties: Escrow = Escrow {
         engagement_id: engagement_id.clone(),
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones,
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    let initialized_escrow = escrow_approver.initialize_escrow(&escrow_properties);
+    let initialized_escrow = escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
     let escrow = escrow_approver.get_escrow();
     assert_eq!(escrow.engagement_id, initialized_escrow.engagement_id);
@@ -114,7 +279,7 @@ fn test_initialize_excrow() {
     );
     assert_eq!(escrow.amount, amount);
     assert_eq!(escrow.platform_fee, platform_fee);
-    assert_eq!(escrow.milestones, escrow_properties.milestones);
+    assert_eq!(escrow_approver.get_milestones(&0, &10), milestones);
     assert_eq!(
         escrow.roles.release_signer,
         escrow_properties.roles.release_signer
@@ -126,7 +291,7 @@ fn test_initialize_excrow() {
     assert_eq!(escrow.roles.receiver, escrow_properties.roles.receiver);
     assert_eq!(escrow.receiver_memo, escrow_properties.receiver_memo);
 
-    let result = escrow_approver.try_initialize_escrow(&escrow_properties);
+    let result = escrow_approver.try_initialize_escrow(&escrow_properties, &milestones);
     assert!(result.is_err());
 }
 
@@ -179,9 +344,7 @@ fn test_update_escrow() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow_2");
     let initial_escrow_properties: Escrow = Escrow {
@@ -189,42 +352,23 @@ fn test_update_escrow() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles: roles.clone(),
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: initial_milestones.clone(),
+        amount,
+        platform_fee,
         flags: flags.clone(),
         trustline: trustline.clone(),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&initial_escrow_properties);
+    escrow_approver.initialize_escrow(&initial_escrow_properties, &initial_milestones);
 
     // Create a new updated escrow properties
-    let new_milestones = vec![
-        &env,
-        Milestone {
-            description: String::from_str(&env, "First milestone updated"),
-            status: String::from_str(&env, "Pending"),
-            evidence: String::from_str(&env, "Initial evidence"),
-            approved: false,
-        },
-        Milestone {
-            description: String::from_str(&env, "Second milestone updated"),
-            status: String::from_str(&env, "Pending"),
-            evidence: String::from_str(&env, "Initial evidence"),
-            approved: false,
-        },
-        Milestone {
-            description: String::from_str(&env, "Third milestone new"),
-            status: String::from_str(&env, "Pending"),
-            evidence: String::from_str(&env, "Initial evidence"),
-            approved: false,
-        },
-    ];
-
     let updated_escrow_properties: Escrow = Escrow {
         engagement_id: engagement_id.clone(),
         title: String::from_str(&env, "Test Escrow Updated"),
@@ -232,15 +376,33 @@ fn test_update_escrow() {
         roles,
         amount: amount * 2,
         platform_fee: platform_fee * 2,
-        milestones: new_milestones.clone(),
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
-    // Update escrow properties
-    let _updated_escrow =
-        escrow_approver.update_escrow(&platform_address, &updated_escrow_properties);
+    // Propose the update; economic changes need the affected parties to approve
+    let proposal = escrow_approver
+        .update_escrow(&platform_address, &updated_escrow_properties)
+        .unwrap();
+    assert_eq!(
+        proposal.required_approvals,
+        vec![
+            &env,
+            approver_address.clone(),
+            service_provider_address.clone()
+        ]
+    );
+    assert_eq!(escrow_approver.get_escrow().amount, amount);
+
+    escrow_approver.approve_escrow_update(&approver_address);
+    assert!(escrow_approver.get_pending_update().is_some());
+    escrow_approver.approve_escrow_update(&service_provider_address);
+    assert!(escrow_approver.get_pending_update().is_none());
 
     // Verify updated escrow properties
     let escrow = escrow_approver.get_escrow();
@@ -248,7 +410,8 @@ fn test_update_escrow() {
     assert_eq!(escrow.description, updated_escrow_properties.description);
     assert_eq!(escrow.amount, updated_escrow_properties.amount);
     assert_eq!(escrow.platform_fee, updated_escrow_properties.platform_fee);
-    assert_eq!(escrow.milestones, updated_escrow_properties.milestones);
+    // Milestones are managed through their own entrypoints and are left untouched
+    assert_eq!(escrow_approver.get_milestones(&0, &10), initial_milestones);
     assert_eq!(
         escrow.roles.release_signer,
         updated_escrow_properties.roles.release_signer
@@ -319,9 +482,7 @@ fn test_change_milestone_status_and_approved() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow");
     let escrow_properties: Escrow = Escrow {
@@ -329,43 +490,45 @@ fn test_change_milestone_status_and_approved() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles: roles.clone(),
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: initial_milestones.clone(),
+        amount,
+        platform_fee,
         flags: flags.clone(),
         trustline: trustline.clone(),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &initial_milestones);
 
     // Change milestone status (valid case)
     let new_status = String::from_str(&env, "completed");
     let new_evidence = Some(String::from_str(&env, "New evidence"));
     escrow_approver.change_milestone_status(
-        &(0 as i128),
+        &0_i128,
         &new_status,
         &new_evidence,
         &service_provider_address,
     );
 
-    let updated_escrow = escrow_approver.get_escrow();
-    assert_eq!(updated_escrow.milestones.get(0).unwrap().status, new_status);
+    let updated_milestone = escrow_approver.get_milestone(&0);
+    assert_eq!(updated_milestone.status, new_status);
     assert_eq!(
-        updated_escrow.milestones.get(0).unwrap().evidence,
+        updated_milestone.evidence,
         String::from_str(&env, "New evidence")
     );
 
     // Change milestone approved (valid case)
-    escrow_approver.approve_milestone(&(0 as i128), &approver_address);
+    escrow_approver.approve_milestone(&0_i128, &approver_address);
 
-    let final_escrow = escrow_approver.get_escrow();
-    assert!(final_escrow.milestones.get(0).unwrap().approved);
+    assert!(escrow_approver.get_milestone(&0).approved);
 
-    let invalid_index = 10 as i128;
+    let invalid_index = 10_i128;
     let new_status = String::from_str(&env, "completed");
     let new_evidence = Some(String::from_str(&env, "New evidence"));
 
@@ -384,7 +547,7 @@ fn test_change_milestone_status_and_approved() {
 
     // Test for `change_status` by invalid service provider
     let result = escrow_approver.try_change_milestone_status(
-        &(0 as i128),
+        &0_i128,
         &new_status,
         &new_evidence,
         &unauthorized_address,
@@ -392,7 +555,7 @@ fn test_change_milestone_status_and_approved() {
     assert!(result.is_err());
 
     // Test for `change_approved` by invalid approver
-    let result = escrow_approver.try_approve_milestone(&(0 as i128), &unauthorized_address);
+    let result = escrow_approver.try_approve_milestone(&0_i128, &unauthorized_address);
     assert!(result.is_err());
 }
 
@@ -413,7 +576,7 @@ fn test_release_funds_successful_flow() {
     let usdc_token = create_usdc_token(&env, &admin);
 
     let amount: i128 = 100_000_000;
-    usdc_token.1.mint(&approver_address, &(amount as i128));
+    usdc_token.1.mint(&approver_address, &amount);
 
     let platform_fee = 5 * 100;
 
@@ -448,9 +611,7 @@ fn test_release_funds_successful_flow() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow_1");
     let escrow_properties: Escrow = Escrow {
@@ -458,32 +619,32 @@ fn test_release_funds_successful_flow() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
-    usdc_token
-        .1
-        .mint(&escrow_approver.address, &(amount as i128));
+    usdc_token.1.mint(&escrow_approver.address, &amount);
 
     escrow_approver.approve_milestone(&0, &approver_address);
     escrow_approver.approve_milestone(&1, &approver_address);
     escrow_approver.release_funds(&release_signer_address, &trustless_work_address);
 
-    let total_amount = amount as i128;
-    let trustless_work_commission = ((total_amount * 30) / 10000) as i128;
-    let platform_commission = (total_amount * platform_fee as i128) / 10000 as i128;
-    let receiver_amount =
-        (total_amount - (trustless_work_commission + platform_commission)) as i128;
+    let total_amount = amount;
+    let trustless_work_commission = (total_amount * 30) / 10000;
+    let platform_commission = (total_amount * platform_fee as i128) / 10000_i128;
+    let receiver_amount = total_amount - (trustless_work_commission + platform_commission);
 
     assert_eq!(
         usdc_token.0.balance(&trustless_work_address),
@@ -568,31 +729,30 @@ fn test_release_funds_milestones_incomplete() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let escrow_properties: Escrow = Escrow {
         engagement_id: engagement_id_incomplete_milestones.clone(),
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: incomplete_milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &incomplete_milestones);
 
-    usdc_token
-        .1
-        .mint(&escrow_approver.address, &(amount as i128));
+    usdc_token.1.mint(&escrow_approver.address, &amount);
     escrow_approver.approve_milestone(&0, &approver_address);
     // Try to distribute earnings with incomplete milestones (should fail)
     let result =
@@ -618,7 +778,7 @@ fn test_release_funds_same_receiver_as_provider() {
     let usdc_token = create_usdc_token(&env, &admin);
 
     let amount: i128 = 100_000_000;
-    usdc_token.1.mint(&approver_address, &(amount as i128));
+    usdc_token.1.mint(&approver_address, &amount);
 
     let platform_fee = 5 * 100;
 
@@ -647,9 +807,7 @@ fn test_release_funds_same_receiver_as_provider() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow_same_receiver");
     let escrow_properties: Escrow = Escrow {
@@ -657,31 +815,31 @@ fn test_release_funds_same_receiver_as_provider() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
-    usdc_token
-        .1
-        .mint(&escrow_approver.address, &(amount as i128));
+    usdc_token.1.mint(&escrow_approver.address, &amount);
 
     escrow_approver.approve_milestone(&0, &approver_address);
     escrow_approver.release_funds(&release_signer_address, &trustless_work_address);
 
-    let total_amount = amount as i128;
-    let trustless_work_commission = ((total_amount * 30) / 10000) as i128;
-    let platform_commission = (total_amount * platform_fee as i128) / 10000 as i128;
-    let service_provider_amount =
-        (total_amount - (trustless_work_commission + platform_commission)) as i128;
+    let total_amount = amount;
+    let trustless_work_commission = (total_amount * 30) / 10000;
+    let platform_commission = (total_amount * platform_fee as i128) / 10000_i128;
+    let service_provider_amount = total_amount - (trustless_work_commission + platform_commission);
 
     assert_eq!(
         usdc_token.0.balance(&trustless_work_address),
@@ -727,7 +885,7 @@ fn test_release_funds_invalid_receiver_fallback() {
     let usdc_token = create_usdc_token(&env, &admin);
 
     let amount: i128 = 100_000_000;
-    usdc_token.1.mint(&approver_address, &(amount as i128));
+    usdc_token.1.mint(&approver_address, &amount);
 
     let platform_fee = 5 * 100;
 
@@ -756,9 +914,7 @@ fn test_release_funds_invalid_receiver_fallback() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow_receiver");
     let escrow_properties: Escrow = Escrow {
@@ -766,31 +922,31 @@ fn test_release_funds_invalid_receiver_fallback() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
-    usdc_token
-        .1
-        .mint(&escrow_approver.address, &(amount as i128));
+    usdc_token.1.mint(&escrow_approver.address, &amount);
 
     escrow_approver.approve_milestone(&0, &approver_address);
     escrow_approver.release_funds(&release_signer_address, &trustless_work_address);
 
-    let total_amount = amount as i128;
-    let trustless_work_commission = ((total_amount * 30) / 10000) as i128;
-    let platform_commission = (total_amount * platform_fee as i128) / 10000 as i128;
-    let receiver_amount =
-        (total_amount - (trustless_work_commission + platform_commission)) as i128;
+    let total_amount = amount;
+    let trustless_work_commission = (total_amount * 30) / 10000;
+    let platform_commission = (total_amount * platform_fee as i128) / 10000_i128;
+    let receiver_amount = total_amount - (trustless_work_commission + platform_commission);
 
     assert_eq!(
         usdc_token.0.balance(&trustless_work_address),
@@ -869,27 +1025,28 @@ fn test_dispute_management() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let escrow_properties: Escrow = Escrow {
         engagement_id: engagement_id.clone(),
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
     let escrow = escrow_approver.get_escrow();
     assert!(!escrow.flags.disputed);
@@ -899,7 +1056,7 @@ fn test_dispute_management() {
     let escrow_after_change = escrow_approver.get_escrow();
     assert!(escrow_after_change.flags.disputed);
 
-    usdc_token.1.mint(&approver_address, &(amount as i128));
+    usdc_token.1.mint(&approver_address, &amount);
     // Test block on distributing earnings during dispute
     let result =
         escrow_approver.try_release_funds(&release_signer_address, &trustless_work_address);
@@ -909,6 +1066,17 @@ fn test_dispute_management() {
 
     let escrow_after_second_change = escrow_approver.get_escrow();
     assert!(escrow_after_second_change.flags.disputed);
+
+    // Nothing was deposited, so there is nothing to distribute
+    let result = escrow_approver.try_resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &Map::new(&env),
+    );
+    assert_eq!(
+        result.err(),
+        Some(Ok(ContractError::TotalAmountCannotBeZero))
+    );
 }
 
 #[test]
@@ -927,7 +1095,7 @@ fn test_dispute_resolution_process() {
     let usdc_token = create_usdc_token(&env, &admin);
 
     let amount: i128 = 100_000_000;
-    usdc_token.1.mint(&approver_address, &(amount as i128));
+    usdc_token.1.mint(&approver_address, &amount);
 
     let platform_fee = 5 * 100;
 
@@ -956,9 +1124,7 @@ fn test_dispute_resolution_process() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_dispute_resolution");
     let escrow_properties: Escrow = Escrow {
@@ -966,18 +1132,21 @@ fn test_dispute_resolution_process() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
     usdc_token
         .0
@@ -995,7 +1164,7 @@ fn test_dispute_resolution_process() {
     let result = escrow_approver.try_resolve_dispute(
         &approver_address,
         &trustless_work_address,
-        &wrong_dist,
+        &token_distributions(&env, &usdc_token.0.address, &wrong_dist),
     );
     assert!(result.is_err());
 
@@ -1011,7 +1180,7 @@ fn test_dispute_resolution_process() {
     let incorrect_dispute_resolution_result = escrow_approver.try_resolve_dispute(
         &dispute_resolver_address,
         &trustless_work_address,
-        &incorrect_dist,
+        &token_distributions(&env, &usdc_token.0.address, &incorrect_dist),
     );
 
     assert!(incorrect_dispute_resolution_result.is_err());
@@ -1020,7 +1189,7 @@ fn test_dispute_resolution_process() {
     let dispute_resolution_with_incorrect_funds = escrow_approver.try_resolve_dispute(
         &dispute_resolver_address,
         &trustless_work_address,
-        &empty_dist,
+        &token_distributions(&env, &usdc_token.0.address, &empty_dist),
     );
 
     assert!(dispute_resolution_with_incorrect_funds.is_err());
@@ -1031,16 +1200,20 @@ fn test_dispute_resolution_process() {
     let mut ok_dist = Map::new(&env);
     ok_dist.set(approver_address.clone(), approver_funds);
     ok_dist.set(service_provider_address.clone(), receiver_funds);
-    escrow_approver.resolve_dispute(&dispute_resolver_address, &trustless_work_address, &ok_dist);
+    escrow_approver.resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &token_distributions(&env, &usdc_token.0.address, &ok_dist),
+    );
 
     // Verify dispute was resolved
     let escrow_after_resolution = escrow_approver.get_escrow();
     assert!(!escrow_after_resolution.flags.disputed);
     assert!(escrow_after_resolution.flags.resolved);
 
-    let total_amount = amount as i128;
-    let trustless_work_commission = ((total_amount * 30) / 10000) as i128;
-    let platform_commission = (total_amount * platform_fee as i128) / 10000 as i128;
+    let total_amount = amount;
+    let trustless_work_commission = (total_amount * 30) / 10000;
+    let platform_commission = (total_amount * platform_fee as i128) / 10000_i128;
     let remaining_amount = total_amount - (trustless_work_commission + platform_commission);
 
     let platform_amount = platform_commission;
@@ -1118,9 +1291,7 @@ fn test_fund_escrow_successful_deposit() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow_fund");
     let escrow_properties: Escrow = Escrow {
@@ -1128,18 +1299,21 @@ fn test_fund_escrow_successful_deposit() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
     // Check initial balances
     assert_eq!(usdc_token.0.balance(&approver_address), amount);
@@ -1147,10 +1321,20 @@ fn test_fund_escrow_successful_deposit() {
 
     let deposit_amount = amount / 2;
 
-    let test_fund = escrow_approver.try_fund_escrow(&approver_address, &escrow_properties, &0);
+    let test_fund = escrow_approver.try_fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &0,
+    );
     assert!(test_fund.is_err());
 
-    escrow_approver.fund_escrow(&approver_address, &escrow_properties, &deposit_amount);
+    escrow_approver.fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &deposit_amount,
+    );
 
     // Check balances after deposit
     assert_eq!(
@@ -1163,7 +1347,12 @@ fn test_fund_escrow_successful_deposit() {
     );
 
     // Deposit remaining amount
-    escrow_approver.fund_escrow(&approver_address, &escrow_properties, &deposit_amount);
+    escrow_approver.fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &deposit_amount,
+    );
 
     assert_eq!(usdc_token.0.balance(&approver_address), 0);
     assert_eq!(usdc_token.0.balance(&escrow_approver.address), amount);
@@ -1216,9 +1405,7 @@ fn test_fund_escrow_signer_insufficient_funds_error() {
         resolved: false,
     };
 
-    let trustline: Trustline = Trustline {
-        address: usdc_token.0.address.clone(),
-    };
+    let trustline: Trustline = token_trustline(&usdc_token.0);
 
     let engagement_id = String::from_str(&env, "test_escrow_insufficient_funds");
     let escrow_properties: Escrow = Escrow {
@@ -1226,24 +1413,32 @@ fn test_fund_escrow_signer_insufficient_funds_error() {
         title: String::from_str(&env, "Test Escrow"),
         description: String::from_str(&env, "Test Escrow Description"),
         roles,
-        amount: amount,
-        platform_fee: platform_fee,
-        milestones: milestones.clone(),
+        amount,
+        platform_fee,
         flags,
         trustline,
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_approver = test_data.client;
 
-    escrow_approver.initialize_escrow(&escrow_properties);
+    escrow_approver.initialize_escrow(&escrow_properties, &milestones);
 
     // Check initial balance
     assert_eq!(usdc_token.0.balance(&approver_address), small_amount);
 
     // Try to deposit more than the approver has (should fail)
-    let result = escrow_approver.try_fund_escrow(&approver_address, &escrow_properties, &amount);
+    let result = escrow_approver.try_fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &amount,
+    );
     assert!(result.is_err());
 
     // Verify balances didn't change
@@ -1293,22 +1488,23 @@ fn test_dispute_escrow_authorized_and_unauthorized() {
         roles,
         amount: 10_000_000,
         platform_fee: 0,
-        milestones,
         flags: Flags {
             disputed: false,
             released: false,
             resolved: false,
         },
-        trustline: Trustline {
-            address: usdc_token.0.address.clone(),
-        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     let test_data = create_escrow_contract(&env);
     let escrow_client_1 = test_data.client;
 
-    escrow_client_1.initialize_escrow(&escrow_base);
+    escrow_client_1.initialize_escrow(&escrow_base, &milestones);
     escrow_client_1.dispute_escrow(&approver);
 
     let updated_escrow = escrow_client_1.get_escrow();
@@ -1320,7 +1516,7 @@ fn test_dispute_escrow_authorized_and_unauthorized() {
     let test_data = create_escrow_contract(&env);
     let escrow_client_2 = test_data.client;
 
-    escrow_client_2.initialize_escrow(&escrow_base);
+    escrow_client_2.initialize_escrow(&escrow_base, &milestones);
     let result = escrow_client_2.try_dispute_escrow(&unauthorized);
 
     assert!(
@@ -1370,24 +1566,25 @@ fn test_get_multiple_escrow_balances_platform_authorized() {
         roles: roles.clone(),
         amount: 50_000_000,
         platform_fee: 100, // 1%
-        milestones,
         flags: Flags {
             disputed: false,
             released: false,
             resolved: false,
         },
-        trustline: Trustline {
-            address: usdc_token.0.address.clone(),
-        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
         receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
     };
 
     // Deploy two escrow contracts of the same code and initialize both
     let c1 = create_escrow_contract(&env).client;
-    c1.initialize_escrow(&escrow_base);
+    c1.initialize_escrow(&escrow_base, &milestones);
 
     let c2 = create_escrow_contract(&env).client;
-    c2.initialize_escrow(&escrow_base);
+    c2.initialize_escrow(&escrow_base, &milestones);
 
     // Mint funds to both contracts so they have balances
     usdc_token.1.mint(&c1.address, &escrow_base.amount);
@@ -1404,3 +1601,4003 @@ fn test_get_multiple_escrow_balances_platform_authorized() {
         c1.get_multiple_escrow_balances(&vec![&env, c1.address.clone(), c2.address.clone()]);
     assert_eq!(res_two.len(), 2);
 }
+
+#[test]
+fn test_admin_two_step_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    let admin = test_data.admin;
+    let new_admin = Address::generate(&env);
+    let unauthorized = Address::generate(&env);
+
+    assert_eq!(escrow_client.get_admin(), admin);
+    assert_eq!(escrow_client.get_pending_admin(), None);
+
+    let result = escrow_client.try_accept_admin(&new_admin);
+    assert_eq!(result, Err(Ok(ContractError::NoPendingProposal)));
+
+    let result = escrow_client.try_propose_admin(&unauthorized, &new_admin);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+
+    escrow_client.propose_admin(&admin, &new_admin);
+    assert_eq!(escrow_client.get_pending_admin(), Some(new_admin.clone()));
+    // The current admin keeps control until the transfer is accepted
+    assert_eq!(escrow_client.get_admin(), admin);
+
+    let result = escrow_client.try_accept_admin(&unauthorized);
+    assert_eq!(result, Err(Ok(ContractError::OnlyProposedAddressCanAccept)));
+
+    escrow_client.accept_admin(&new_admin);
+    assert_eq!(escrow_client.get_admin(), new_admin);
+    assert_eq!(escrow_client.get_pending_admin(), None);
+
+    let result = escrow_client.try_set_protocol_fee(&admin, &10);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+}
+
+#[test]
+fn test_admin_protocol_controls() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    let admin = test_data.admin;
+    let unauthorized = Address::generate(&env);
+    let fee_collector = Address::generate(&env);
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+
+    assert_eq!(escrow_client.get_protocol_fee(), 30);
+    escrow_client.set_protocol_fee(&admin, &50);
+    assert_eq!(escrow_client.get_protocol_fee(), 50);
+
+    let result = escrow_client.try_set_protocol_fee(&admin, &101);
+    assert_eq!(result, Err(Ok(ContractError::ProtocolFeeTooHigh)));
+
+    assert_eq!(escrow_client.get_fee_collector(), None);
+    let result = escrow_client.try_set_fee_collector(&unauthorized, &fee_collector);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    escrow_client.set_fee_collector(&admin, &fee_collector);
+    assert_eq!(escrow_client.get_fee_collector(), Some(fee_collector));
+
+    assert!(escrow_client.get_wasm_allowlist().is_empty());
+    escrow_client.set_wasm_hash_allowed(&admin, &wasm_hash, &true);
+    escrow_client.set_wasm_hash_allowed(&admin, &wasm_hash, &true);
+    assert_eq!(
+        escrow_client.get_wasm_allowlist(),
+        vec![&env, wasm_hash.clone()]
+    );
+    escrow_client.set_wasm_hash_allowed(&admin, &wasm_hash, &false);
+    assert!(escrow_client.get_wasm_allowlist().is_empty());
+
+    assert!(!escrow_client.is_protocol_paused());
+    let result = escrow_client.try_set_protocol_paused(&unauthorized, &true);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    escrow_client.set_protocol_paused(&admin, &true);
+    assert!(escrow_client.is_protocol_paused());
+}
+
+#[test]
+fn test_release_funds_with_protocol_fee_and_fee_collector() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let fee_collector = Address::generate(&env);
+    let wrong_trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let amount: i128 = 100_000_000;
+    let platform_fee = 5 * 100;
+    let protocol_fee = 50;
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_fee_collector"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+
+    escrow_client.set_protocol_fee(&test_data.admin, &protocol_fee);
+    escrow_client.set_fee_collector(&test_data.admin, &fee_collector);
+
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+    escrow_client.approve_milestone(&0, &approver_address);
+
+    let result =
+        escrow_client.try_release_funds(&release_signer_address, &wrong_trustless_work_address);
+    assert_eq!(result, Err(Ok(ContractError::InvalidTrustlessWorkAddress)));
+
+    escrow_client.release_funds(&release_signer_address, &fee_collector);
+
+    let trustless_work_commission = (amount * protocol_fee as i128) / 10000;
+    let platform_commission = (amount * platform_fee as i128) / 10000;
+
+    assert_eq!(
+        usdc_token.0.balance(&fee_collector),
+        trustless_work_commission
+    );
+    assert_eq!(usdc_token.0.balance(&platform_address), platform_commission);
+    assert_eq!(
+        usdc_token.0.balance(&service_provider_address),
+        amount - trustless_work_commission - platform_commission
+    );
+}
+
+#[test]
+fn test_governed_escrow_reads_protocol_settings_from_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let fee_collector = Address::generate(&env);
+    let new_protocol_admin = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let amount: i128 = 100_000_000;
+    let platform_fee = 5 * 100;
+    let protocol_fee = 50;
+
+    let governance = create_escrow_contract(&env);
+    let governance_client = governance.client;
+    let protocol_admin = governance.admin;
+    let escrow_client = create_governed_escrow_contract(&env, &governance_client.address);
+
+    assert_eq!(governance_client.get_governance(), None);
+    assert_eq!(
+        escrow_client.get_governance(),
+        Some(governance_client.address.clone())
+    );
+    assert_eq!(escrow_client.get_admin(), protocol_admin);
+
+    governance_client.set_protocol_fee(&protocol_admin, &protocol_fee);
+    governance_client.set_fee_collector(&protocol_admin, &fee_collector);
+    assert_eq!(escrow_client.get_protocol_fee(), protocol_fee);
+    assert_eq!(
+        escrow_client.get_fee_collector(),
+        Some(fee_collector.clone())
+    );
+
+    // Settings can only be changed where they are kept
+    assert_eq!(
+        escrow_client
+            .try_set_protocol_fee(&protocol_admin, &10)
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_fee_collector(&protocol_admin, &protocol_admin)
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_wasm_hash_allowed(&protocol_admin, &BytesN::from_array(&env, &[1; 32]), &true)
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+    assert_eq!(
+        escrow_client
+            .try_propose_admin(&protocol_admin, &new_protocol_admin)
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+
+    // An admin transfer on the governance contract carries over to every escrow
+    governance_client.propose_admin(&protocol_admin, &new_protocol_admin);
+    governance_client.accept_admin(&new_protocol_admin);
+    assert_eq!(escrow_client.get_admin(), new_protocol_admin);
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_governed"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles: Roles {
+            approver: approver_address.clone(),
+            service_provider: service_provider_address.clone(),
+            platform_address: platform_address.clone(),
+            release_signer: release_signer_address.clone(),
+            dispute_resolver: Address::generate(&env),
+            receiver: service_provider_address.clone(),
+        },
+        amount,
+        platform_fee,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+    escrow_client.approve_milestone(&0, &approver_address);
+
+    // The escrow pause stays per escrow and answers to the protocol admin
+    escrow_client.pause_escrow(&new_protocol_admin);
+    assert!(escrow_client.is_escrow_paused());
+    assert!(!governance_client.is_escrow_paused());
+    escrow_client.unpause_escrow(&new_protocol_admin);
+
+    escrow_client.release_funds(&release_signer_address, &fee_collector);
+
+    let trustless_work_commission = (amount * protocol_fee as i128) / 10000;
+    assert_eq!(
+        usdc_token.0.balance(&fee_collector),
+        trustless_work_commission
+    );
+}
+
+#[test]
+fn test_pause_blocks_value_moving_entrypoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let amount: i128 = 100_000_000;
+    usdc_token.1.mint(&approver_address, &amount);
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_pause"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 5 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let governance = create_escrow_contract(&env);
+    let governance_client = governance.client;
+    let contract_admin = governance.admin;
+    let escrow_client = create_governed_escrow_contract(&env, &governance_client.address);
+
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    let result = escrow_client.try_pause_escrow(&approver_address);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+
+    escrow_client.pause_escrow(&contract_admin);
+    assert!(escrow_client.is_escrow_paused());
+
+    let result = escrow_client.try_fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &amount,
+    );
+    assert_eq!(result, Err(Ok(ContractError::EscrowPaused)));
+
+    escrow_client.unpause_escrow(&contract_admin);
+    escrow_client.fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &amount,
+    );
+    escrow_client.approve_milestone(&0, &approver_address);
+
+    // The protocol pause is kept on the governance contract and covers all its escrows
+    assert_eq!(
+        escrow_client
+            .try_set_protocol_paused(&contract_admin, &true)
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+    governance_client.set_protocol_paused(&contract_admin, &true);
+    assert!(escrow_client.is_protocol_paused());
+
+    let result = escrow_client.try_release_funds(&release_signer_address, &trustless_work_address);
+    assert_eq!(result, Err(Ok(ContractError::ProtocolPaused)));
+
+    // Reads, TTL extension and non value-moving actions keep working while paused
+    assert_eq!(escrow_client.get_escrow().amount, amount);
+    escrow_client.extend_contract_ttl(&platform_address, &1000, &0, &MAX_TTL_ENTRIES_PER_PAGE);
+    escrow_client.dispute_escrow(&approver_address);
+
+    let mut distributions = Map::new(&env);
+    distributions.set(approver_address.clone(), amount);
+    let result = escrow_client.try_resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &token_distributions(&env, &usdc_token.0.address, &distributions),
+    );
+    assert_eq!(result, Err(Ok(ContractError::ProtocolPaused)));
+
+    governance_client.set_protocol_paused(&contract_admin, &false);
+    escrow_client.resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &token_distributions(&env, &usdc_token.0.address, &distributions),
+    );
+    assert!(escrow_client.get_escrow().flags.resolved);
+}
+
+#[test]
+fn test_two_step_role_rotation_on_funded_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let receiver_address = Address::generate(&env);
+    let new_service_provider = Address::generate(&env);
+    let new_receiver = Address::generate(&env);
+    let unauthorized = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: receiver_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_role_rotation"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+
+    // The current holder proposes and the new address accepts
+    let result =
+        escrow_client.try_accept_role_change(&Role::ServiceProvider, &new_service_provider);
+    assert_eq!(result, Err(Ok(ContractError::NoPendingProposal)));
+
+    escrow_client.propose_role_change(
+        &vec![&env, service_provider_address.clone()],
+        &Role::ServiceProvider,
+        &new_service_provider,
+    );
+    assert_eq!(
+        escrow_client
+            .get_pending_role_change(&Role::ServiceProvider)
+            .map(|proposal| proposal.new_address),
+        Some(new_service_provider.clone())
+    );
+
+    let result = escrow_client.try_accept_role_change(&Role::ServiceProvider, &unauthorized);
+    assert_eq!(result, Err(Ok(ContractError::OnlyProposedAddressCanAccept)));
+
+    escrow_client.accept_role_change(&Role::ServiceProvider, &new_service_provider);
+    assert_eq!(
+        escrow_client.get_escrow().roles.service_provider,
+        new_service_provider
+    );
+    assert_eq!(
+        escrow_client.get_pending_role_change(&Role::ServiceProvider),
+        None
+    );
+
+    // A single party cannot rotate a role it does not hold
+    let result = escrow_client.try_propose_role_change(
+        &vec![&env, approver_address.clone()],
+        &Role::Approver,
+        &unauthorized,
+    );
+    assert_eq!(result, Ok(Ok(())));
+    let result = escrow_client.try_propose_role_change(
+        &vec![&env, service_provider_address.clone()],
+        &Role::Approver,
+        &unauthorized,
+    );
+    assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+
+    // A quorum cannot redirect the funds of a funded escrow without the receiver
+    let result = escrow_client.try_propose_role_change(
+        &vec![&env, approver_address.clone(), platform_address.clone()],
+        &Role::Receiver,
+        &new_receiver,
+    );
+    assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+    let result = escrow_client.try_propose_role_change(
+        &vec![&env, approver_address.clone(), new_service_provider.clone()],
+        &Role::PlatformAddress,
+        &unauthorized,
+    );
+    assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+    for role in [Role::DisputeResolver, Role::ReleaseSigner] {
+        let result = escrow_client.try_propose_role_change(
+            &vec![
+                &env,
+                approver_address.clone(),
+                new_service_provider.clone(),
+                platform_address.clone(),
+            ],
+            &role,
+            &unauthorized,
+        );
+        assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+    }
+
+    // Two quorum members can rotate the approver or service provider if its key is lost
+    let new_approver = Address::generate(&env);
+    escrow_client.propose_role_change(
+        &vec![&env, new_service_provider.clone(), platform_address.clone()],
+        &Role::Approver,
+        &new_approver,
+    );
+    escrow_client.accept_role_change(&Role::Approver, &new_approver);
+
+    escrow_client.propose_role_change(
+        &vec![&env, receiver_address.clone()],
+        &Role::Receiver,
+        &new_receiver,
+    );
+    escrow_client.accept_role_change(&Role::Receiver, &new_receiver);
+
+    let escrow = escrow_client.get_escrow();
+    assert_eq!(escrow.roles.receiver, new_receiver);
+    assert_eq!(escrow.roles.approver, new_approver);
+}
+
+#[test]
+fn test_role_change_proposals_expire_and_stop_during_disputes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let receiver_address = Address::generate(&env);
+    let new_receiver = Address::generate(&env);
+    let new_service_provider = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_role_rotation_limits"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles: Roles {
+            approver: approver_address.clone(),
+            service_provider: service_provider_address.clone(),
+            platform_address: Address::generate(&env),
+            release_signer: Address::generate(&env),
+            dispute_resolver: Address::generate(&env),
+            receiver: receiver_address.clone(),
+        },
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+
+    // An unaccepted proposal lapses after ROLE_CHANGE_PROPOSAL_TTL_LEDGERS
+    escrow_client.propose_role_change(
+        &vec![&env, receiver_address.clone()],
+        &Role::Receiver,
+        &new_receiver,
+    );
+    let proposal = escrow_client
+        .get_pending_role_change(&Role::Receiver)
+        .unwrap();
+    assert_eq!(
+        proposal.expiration_ledger,
+        env.ledger().sequence() + ROLE_CHANGE_PROPOSAL_TTL_LEDGERS
+    );
+
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = proposal.expiration_ledger + 1);
+    assert_eq!(escrow_client.get_pending_role_change(&Role::Receiver), None);
+    assert_eq!(
+        escrow_client
+            .try_accept_role_change(&Role::Receiver, &new_receiver)
+            .err(),
+        Some(Ok(ContractError::NoPendingProposal))
+    );
+
+    // No role changes hands while a dispute is open, even with the holder's consent
+    escrow_client.propose_role_change(
+        &vec![&env, service_provider_address.clone()],
+        &Role::ServiceProvider,
+        &new_service_provider,
+    );
+    escrow_client.dispute_escrow(&approver_address);
+    assert_eq!(
+        escrow_client
+            .try_accept_role_change(&Role::ServiceProvider, &new_service_provider)
+            .err(),
+        Some(Ok(ContractError::EscrowOpenedForDisputeResolution))
+    );
+    assert_eq!(
+        escrow_client
+            .try_propose_role_change(
+                &vec![&env, receiver_address.clone()],
+                &Role::Receiver,
+                &new_receiver,
+            )
+            .err(),
+        Some(Ok(ContractError::EscrowOpenedForDisputeResolution))
+    );
+    assert_eq!(escrow_client.get_escrow().roles.receiver, receiver_address);
+}
+
+#[test]
+fn test_role_change_requires_holder_or_quorum_signatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let escrow_properties = default_escrow(&env, token_trustline(&usdc_token.0));
+    let roles = escrow_properties.roles.clone();
+    let new_address = Address::generate(&env);
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    // Fund-directing roles need their current holder, and the holder must actually sign
+    for (role, holder) in [
+        (Role::Receiver, &roles.receiver),
+        (Role::PlatformAddress, &roles.platform_address),
+        (Role::ReleaseSigner, &roles.release_signer),
+        (Role::DisputeResolver, &roles.dispute_resolver),
+    ] {
+        let proposers = vec![&env, holder.clone()];
+        mock_signers(
+            &env,
+            &escrow_client.address,
+            "propose_role_change",
+            (proposers.clone(), role, new_address.clone()).into_val(&env),
+            &[&roles.approver],
+        );
+        assert_eq!(
+            escrow_client
+                .try_propose_role_change(&proposers, &role, &new_address)
+                .err(),
+            Some(Err(InvokeError::Abort))
+        );
+        assert_eq!(escrow_client.get_pending_role_change(&role), None);
+
+        env.mock_all_auths();
+        let result = escrow_client.try_propose_role_change(
+            &vec![&env, roles.approver.clone(), roles.service_provider.clone()],
+            &role,
+            &new_address,
+        );
+        assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+
+        escrow_client.propose_role_change(&proposers, &role, &new_address);
+        assert_eq!(authorized_signers(&env), std::vec![holder.clone()]);
+    }
+
+    // Approver and service provider can also be rotated by two of the three quorum members
+    for (role, quorum) in [
+        (
+            Role::Approver,
+            [&roles.service_provider, &roles.platform_address],
+        ),
+        (
+            Role::ServiceProvider,
+            [&roles.approver, &roles.platform_address],
+        ),
+    ] {
+        let proposers = vec![&env, quorum[0].clone(), quorum[1].clone()];
+        mock_signers(
+            &env,
+            &escrow_client.address,
+            "propose_role_change",
+            (proposers.clone(), role, new_address.clone()).into_val(&env),
+            &[quorum[0]],
+        );
+        assert_eq!(
+            escrow_client
+                .try_propose_role_change(&proposers, &role, &new_address)
+                .err(),
+            Some(Err(InvokeError::Abort))
+        );
+
+        env.mock_all_auths();
+        let result = escrow_client.try_propose_role_change(
+            &vec![&env, quorum[0].clone()],
+            &role,
+            &new_address,
+        );
+        assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+
+        escrow_client.propose_role_change(&proposers, &role, &new_address);
+        assert_eq!(
+            authorized_signers(&env),
+            std::vec![quorum[0].clone(), quorum[1].clone()]
+        );
+    }
+
+    // Only the proposed address can accept, by signing itself
+    mock_signers(
+        &env,
+        &escrow_client.address,
+        "accept_role_change",
+        (Role::Receiver, new_address.clone()).into_val(&env),
+        &[&roles.receiver],
+    );
+    assert_eq!(
+        escrow_client
+            .try_accept_role_change(&Role::Receiver, &new_address)
+            .err(),
+        Some(Err(InvokeError::Abort))
+    );
+    assert_eq!(escrow_client.get_escrow().roles.receiver, roles.receiver);
+
+    env.mock_all_auths();
+    escrow_client.accept_role_change(&Role::Receiver, &new_address);
+    assert_eq!(authorized_signers(&env), std::vec![new_address.clone()]);
+    assert_eq!(escrow_client.get_escrow().roles.receiver, new_address);
+}
+
+#[test]
+fn test_update_escrow_requires_counterparty_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let receiver_address = Address::generate(&env);
+    let new_receiver = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: receiver_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_update_consent"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles: roles.clone(),
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    // Cosmetic changes are applied without consent
+    let mut renamed_escrow = escrow_properties.clone();
+    renamed_escrow.title = String::from_str(&env, "Renamed Escrow");
+    assert!(escrow_client
+        .update_escrow(&platform_address, &renamed_escrow)
+        .is_none());
+    assert_eq!(escrow_client.get_escrow().title, renamed_escrow.title);
+
+    // Replacing the receiver needs the current receiver's approval
+    let mut new_receiver_escrow = renamed_escrow.clone();
+    new_receiver_escrow.roles.receiver = new_receiver.clone();
+    let proposal = escrow_client
+        .update_escrow(&platform_address, &new_receiver_escrow)
+        .unwrap();
+    assert_eq!(
+        proposal.required_approvals,
+        vec![&env, receiver_address.clone()]
+    );
+
+    let result = escrow_client.try_approve_escrow_update(&approver_address);
+    assert_eq!(result, Err(Ok(ContractError::UnauthorizedProposalSigner)));
+
+    // Proposals expire
+    env.ledger()
+        .with_mut(|li| li.sequence_number += proposal.expiration_ledger + 1);
+    assert!(escrow_client.get_pending_update().is_none());
+    let result = escrow_client.try_approve_escrow_update(&receiver_address);
+    assert_eq!(result, Err(Ok(ContractError::NoPendingProposal)));
+
+    // Writing the escrow drops a pending proposal so its snapshot cannot undo the write
+    escrow_client.update_escrow(&platform_address, &new_receiver_escrow);
+    assert!(escrow_client.get_pending_update().is_some());
+    escrow_client.set_title_description(
+        &platform_address,
+        &String::from_str(&env, "Retitled Escrow"),
+        &renamed_escrow.description,
+    );
+    assert!(escrow_client.get_pending_update().is_none());
+    let result = escrow_client.try_approve_escrow_update(&receiver_address);
+    assert_eq!(result, Err(Ok(ContractError::NoPendingProposal)));
+    assert_eq!(
+        escrow_client.get_escrow().title,
+        String::from_str(&env, "Retitled Escrow")
+    );
+    assert_eq!(escrow_client.get_escrow().roles.receiver, receiver_address);
+
+    let mut bigger_escrow = renamed_escrow.clone();
+    bigger_escrow.amount = amount * 2;
+    escrow_client.update_escrow(&platform_address, &bigger_escrow);
+    escrow_client.approve_escrow_update(&approver_address);
+    escrow_client.approve_escrow_update(&service_provider_address);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+
+    let result = escrow_client.try_approve_escrow_update(&receiver_address);
+    assert_eq!(result, Err(Ok(ContractError::EscrowHasFunds)));
+    assert_eq!(escrow_client.get_escrow().amount, amount);
+}
+
+#[test]
+fn test_update_escrow_approvals_are_signed_by_each_affected_party() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let escrow_properties = default_escrow(&env, token_trustline(&usdc_token.0));
+    let roles = escrow_properties.roles.clone();
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    // An economic change is only proposed by the platform
+    let bigger_escrow = Escrow {
+        amount: escrow_properties.amount * 2,
+        ..escrow_properties.clone()
+    };
+    mock_signers(
+        &env,
+        &escrow_client.address,
+        "update_escrow",
+        (roles.platform_address.clone(), bigger_escrow.clone()).into_val(&env),
+        &[&roles.approver],
+    );
+    assert_eq!(
+        escrow_client
+            .try_update_escrow(&roles.platform_address, &bigger_escrow)
+            .err(),
+        Some(Err(InvokeError::Abort))
+    );
+
+    env.mock_all_auths();
+    let proposal = escrow_client
+        .update_escrow(&roles.platform_address, &bigger_escrow)
+        .unwrap();
+    assert_eq!(
+        authorized_signers(&env),
+        std::vec![roles.platform_address.clone()]
+    );
+    assert_eq!(
+        proposal.required_approvals,
+        vec![
+            &env,
+            roles.approver.clone(),
+            roles.service_provider.clone(),
+            roles.receiver.clone(),
+        ]
+    );
+
+    // Naming a party is not enough, the party itself has to sign its approval
+    for party in [&roles.service_provider, &roles.platform_address] {
+        mock_signers(
+            &env,
+            &escrow_client.address,
+            "approve_escrow_update",
+            (roles.approver.clone(),).into_val(&env),
+            &[party],
+        );
+        assert_eq!(
+            escrow_client
+                .try_approve_escrow_update(&roles.approver)
+                .err(),
+            Some(Err(InvokeError::Abort))
+        );
+    }
+    env.mock_all_auths();
+    assert!(escrow_client
+        .get_pending_update()
+        .unwrap()
+        .approvals
+        .is_empty());
+
+    let mut signers = std::vec::Vec::new();
+    for party in [&roles.approver, &roles.service_provider, &roles.receiver] {
+        escrow_client.approve_escrow_update(party);
+        signers.extend(authorized_signers(&env));
+    }
+    assert_eq!(
+        signers,
+        std::vec![
+            roles.approver.clone(),
+            roles.service_provider.clone(),
+            roles.receiver.clone(),
+        ]
+    );
+    assert_eq!(escrow_client.get_escrow().amount, bigger_escrow.amount);
+
+    // Replacing a role needs the signature of its current holder
+    let new_receiver = Address::generate(&env);
+    let new_receiver_escrow = Escrow {
+        roles: Roles {
+            receiver: new_receiver.clone(),
+            ..roles.clone()
+        },
+        ..bigger_escrow.clone()
+    };
+    let proposal = escrow_client
+        .update_escrow(&roles.platform_address, &new_receiver_escrow)
+        .unwrap();
+    assert_eq!(
+        proposal.required_approvals,
+        vec![&env, roles.receiver.clone()]
+    );
+
+    mock_signers(
+        &env,
+        &escrow_client.address,
+        "approve_escrow_update",
+        (roles.receiver.clone(),).into_val(&env),
+        &[&new_receiver],
+    );
+    assert_eq!(
+        escrow_client
+            .try_approve_escrow_update(&roles.receiver)
+            .err(),
+        Some(Err(InvokeError::Abort))
+    );
+
+    env.mock_all_auths();
+    escrow_client.approve_escrow_update(&roles.receiver);
+    assert_eq!(authorized_signers(&env), std::vec![roles.receiver.clone()]);
+    assert_eq!(escrow_client.get_escrow().roles.receiver, new_receiver);
+}
+
+#[test]
+fn test_granular_escrow_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let milestone = Milestone {
+        description: String::from_str(&env, "First milestone"),
+        status: String::from_str(&env, "Pending"),
+        evidence: String::from_str(&env, "Initial evidence"),
+        approved: false,
+    };
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_granular_updates"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    escrow_client.initialize_escrow(&escrow_properties, &vec![&env, milestone.clone()]);
+    usdc_token.1.mint(&escrow_client.address, &(amount / 2));
+
+    escrow_client.set_title_description(
+        &platform_address,
+        &String::from_str(&env, "New title"),
+        &String::from_str(&env, "New description"),
+    );
+    let result = escrow_client.try_set_title_description(
+        &approver_address,
+        &String::from_str(&env, "Other title"),
+        &String::from_str(&env, "Other description"),
+    );
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyPlatformAddressExecuteThisFunction))
+    );
+
+    // Milestones can be added while the escrow is funded
+    for _ in 1..10 {
+        escrow_client.add_milestone(&platform_address, &milestone);
+    }
+    assert_eq!(escrow_client.get_milestone_count(), 10);
+
+    escrow_client.remove_milestone(&platform_address, &9);
+
+    // Removing from the middle moves the last milestone into the freed index
+    let last_milestone = Milestone {
+        description: String::from_str(&env, "Last milestone"),
+        ..milestone.clone()
+    };
+    escrow_client.add_milestone(&platform_address, &last_milestone);
+    escrow_client.remove_milestone(&platform_address, &2);
+    let data = find_event_data(&env, "tw_ms_remove");
+    assert_eq!(u32::try_from_val(&env, &data.get(1).unwrap()).unwrap(), 2);
+    assert_eq!(u32::try_from_val(&env, &data.get(2).unwrap()).unwrap(), 9);
+    assert_eq!(escrow_client.get_milestone_count(), 9);
+    assert_eq!(escrow_client.get_milestone(&2), last_milestone);
+    escrow_client.edit_milestone_description(
+        &platform_address,
+        &1,
+        &String::from_str(&env, "Second milestone"),
+    );
+
+    escrow_client.change_milestone_status(
+        &0,
+        &String::from_str(&env, "Completed"),
+        &None,
+        &service_provider_address,
+    );
+    escrow_client.approve_milestone(&0, &approver_address);
+
+    let result = escrow_client.try_edit_milestone_description(
+        &platform_address,
+        &0,
+        &String::from_str(&env, "Changed after approval"),
+    );
+    assert_eq!(
+        result,
+        Err(Ok(
+            ContractError::MilestoneApprovedCantChangeEscrowProperties
+        ))
+    );
+    let result = escrow_client.try_remove_milestone(&platform_address, &0);
+    assert_eq!(
+        result,
+        Err(Ok(
+            ContractError::MilestoneApprovedCantChangeEscrowProperties
+        ))
+    );
+    let result =
+        escrow_client.try_set_amount(&platform_address, &usdc_token.0.address, &(amount * 2));
+    assert_eq!(
+        result,
+        Err(Ok(
+            ContractError::MilestoneApprovedCantChangeEscrowProperties
+        ))
+    );
+
+    let escrow = escrow_client.get_escrow();
+    assert_eq!(escrow.title, String::from_str(&env, "New title"));
+    assert_eq!(escrow_client.get_milestone_count(), 9);
+    assert_eq!(
+        escrow_client.get_milestone(&1).description,
+        String::from_str(&env, "Second milestone")
+    );
+}
+
+#[test]
+fn test_granular_updates_require_counterparty_signatures() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let escrow_properties = default_escrow(&env, token_trustline(&usdc_token.0));
+    let roles = escrow_properties.roles.clone();
+    let milestone = |description: &str| Milestone {
+        description: String::from_str(&env, description),
+        status: String::from_str(&env, "Pending"),
+        evidence: String::from_str(&env, ""),
+        approved: false,
+    };
+    let milestones = vec![
+        &env,
+        milestone("First milestone"),
+        milestone("Second milestone"),
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    let new_description = String::from_str(&env, "Edited milestone");
+    let calls: [(&str, soroban_sdk::Vec<Val>); 4] = [
+        (
+            "add_milestone",
+            (roles.platform_address.clone(), milestone("Third milestone")).into_val(&env),
+        ),
+        (
+            "edit_milestone_description",
+            (
+                roles.platform_address.clone(),
+                0_i128,
+                new_description.clone(),
+            )
+                .into_val(&env),
+        ),
+        (
+            "remove_milestone",
+            (roles.platform_address.clone(), 1_i128).into_val(&env),
+        ),
+        (
+            "set_amount",
+            (
+                roles.platform_address.clone(),
+                usdc_token.0.address.clone(),
+                2_000_000_i128,
+            )
+                .into_val(&env),
+        ),
+    ];
+    let call = |fn_name: &str| match fn_name {
+        "add_milestone" => escrow_client
+            .try_add_milestone(&roles.platform_address, &milestone("Third milestone"))
+            .err(),
+        "edit_milestone_description" => escrow_client
+            .try_edit_milestone_description(&roles.platform_address, &0, &new_description)
+            .err(),
+        "remove_milestone" => escrow_client
+            .try_remove_milestone(&roles.platform_address, &1)
+            .err(),
+        _ => escrow_client
+            .try_set_amount(&roles.platform_address, &usdc_token.0.address, &2_000_000)
+            .err(),
+    };
+
+    let counterparties = [&roles.approver, &roles.service_provider, &roles.receiver];
+    for (fn_name, args) in calls {
+        // The platform cannot make the change with any of the counterparties missing
+        for missing in counterparties {
+            let mut signers = std::vec![&roles.platform_address];
+            signers.extend(counterparties.iter().filter(|party| **party != missing));
+            mock_signers(
+                &env,
+                &escrow_client.address,
+                fn_name,
+                args.clone(),
+                &signers,
+            );
+            assert_eq!(call(fn_name), Some(Err(InvokeError::Abort)));
+        }
+
+        env.mock_all_auths();
+        assert_eq!(call(fn_name), None);
+        assert_eq!(
+            authorized_signers(&env),
+            std::vec![
+                roles.platform_address.clone(),
+                roles.approver.clone(),
+                roles.service_provider.clone(),
+                roles.receiver.clone(),
+            ]
+        );
+    }
+
+    assert_eq!(escrow_client.get_milestone_count(), 2);
+    assert_eq!(escrow_client.get_milestone(&0).description, new_description);
+    assert_eq!(escrow_client.get_escrow().amount, 2_000_000);
+
+    // A cosmetic change is signed by the platform alone
+    escrow_client.set_title_description(
+        &roles.platform_address,
+        &String::from_str(&env, "Renamed Escrow"),
+        &escrow_properties.description,
+    );
+    assert_eq!(
+        authorized_signers(&env),
+        std::vec![roles.platform_address.clone()]
+    );
+}
+
+#[test]
+fn test_set_amount_cannot_drop_below_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: Address::generate(&env),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_set_amount"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &(amount / 2));
+
+    let result = escrow_client.try_set_amount(&platform_address, &usdc_token.0.address, &0);
+    assert_eq!(result, Err(Ok(ContractError::AmountCannotBeZero)));
+    let result =
+        escrow_client.try_set_amount(&platform_address, &usdc_token.0.address, &(amount / 4));
+    assert_eq!(result, Err(Ok(ContractError::EscrowHasFunds)));
+
+    escrow_client.set_amount(&platform_address, &usdc_token.0.address, &(amount * 2));
+    assert_eq!(escrow_client.get_escrow().amount, amount * 2);
+
+    // Fees charged to the funder count towards what the escrow may hold: 3.3% on top here
+    let charge_funder_client = create_escrow_contract(&env).client;
+    charge_funder_client.initialize_escrow(
+        &Escrow {
+            roles: Roles {
+                platform_address: platform_address.clone(),
+                ..generate_roles(&env)
+            },
+            amount,
+            fee_mode: FeeMode::ChargeFunder,
+            ..default_escrow(&env, token_trustline(&usdc_token.0))
+        },
+        &milestones,
+    );
+    usdc_token
+        .1
+        .mint(&charge_funder_client.address, &60_000_000);
+
+    let result =
+        charge_funder_client.try_set_amount(&platform_address, &usdc_token.0.address, &58_000_000);
+    assert_eq!(result, Err(Ok(ContractError::EscrowHasFunds)));
+    charge_funder_client.set_amount(&platform_address, &usdc_token.0.address, &58_500_000);
+    assert_eq!(
+        charge_funder_client
+            .get_required_funding()
+            .get(0)
+            .unwrap()
+            .required_funding,
+        60_430_500
+    );
+}
+
+#[test]
+fn test_paged_milestone_storage() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_paged_milestones"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestone_count: u32 = 120;
+    let milestone = Milestone {
+        description: String::from_str(&env, "Deliverable"),
+        status: String::from_str(&env, "Completed"),
+        evidence: String::from_str(&env, "Initial evidence"),
+        approved: false,
+    };
+    let mut milestones = vec![&env];
+    for _ in 0..=MAX_MILESTONES_PER_CALL {
+        milestones.push_back(milestone.clone());
+    }
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+
+    // Initialization writes a bounded number of milestones; the rest are added later
+    let result = escrow_client.try_initialize_escrow(&escrow_properties, &milestones);
+    assert_eq!(result.err(), Some(Ok(ContractError::TooManyMilestones)));
+
+    milestones.pop_back();
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    // Large engagements are added in bounded batches
+    let result = escrow_client.try_add_milestones(&platform_address, &vec![&env]);
+    assert_eq!(result.err(), Some(Ok(ContractError::NoMilestoneDefined)));
+    let mut oversized_batch = milestones.clone();
+    oversized_batch.push_back(milestone.clone());
+    let result = escrow_client.try_add_milestones(&platform_address, &oversized_batch);
+    assert_eq!(result.err(), Some(Ok(ContractError::TooManyMilestones)));
+    let mut approved_batch = milestones.clone();
+    approved_batch.push_back(Milestone {
+        approved: true,
+        ..milestone.clone()
+    });
+    approved_batch.pop_front();
+    let result = escrow_client.try_add_milestones(&platform_address, &approved_batch);
+    assert_eq!(result.err(), Some(Ok(ContractError::FlagsMustBeFalse)));
+
+    for _ in 1..(milestone_count / MAX_MILESTONES_PER_CALL) {
+        escrow_client.add_milestones(&platform_address, &milestones);
+    }
+    let added = find_event_data(&env, "tw_ms_add");
+    assert_eq!(
+        u32::try_from_val(&env, &added.get(1).unwrap()).unwrap(),
+        milestone_count - MAX_MILESTONES_PER_CALL
+    );
+    assert_eq!(env.events().all().len(), MAX_MILESTONES_PER_CALL);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+
+    assert_eq!(escrow_client.get_milestone_count(), milestone_count);
+    assert_eq!(escrow_client.get_milestones(&0, &20).len(), 20);
+    assert_eq!(escrow_client.get_milestones(&110, &20).len(), 10);
+    assert_eq!(escrow_client.get_milestones(&120, &20).len(), 0);
+    // Pages are capped regardless of the requested limit
+    assert_eq!(escrow_client.get_milestones(&0, &1000).len(), 50);
+
+    let result = escrow_client.try_get_milestone(&(milestone_count as i128));
+    assert_eq!(result, Err(Ok(ContractError::InvalidMileStoneIndex)));
+
+    for milestone_index in 0..(milestone_count - 1) {
+        escrow_client.approve_milestone(&(milestone_index as i128), &approver_address);
+    }
+
+    let result = escrow_client.try_release_funds(&release_signer_address, &trustless_work_address);
+    assert_eq!(result, Err(Ok(ContractError::EscrowNotCompleted)));
+
+    escrow_client.approve_milestone(&((milestone_count - 1) as i128), &approver_address);
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+    assert!(escrow_client.get_escrow().flags.released);
+
+    // The history keeps only the newest entries and pages skip the overwritten ones
+    let history_count = escrow_client.get_history_count();
+    let added_count = milestone_count - MAX_MILESTONES_PER_CALL;
+    assert_eq!(history_count, 1 + added_count + milestone_count + 1);
+    let first_index = history_count - MAX_HISTORY_ENTRIES;
+    assert_eq!(escrow_client.get_first_history_index(), first_index);
+    assert_eq!(escrow_client.get_history(&0, &50).len(), 50 - first_index);
+    assert_eq!(
+        escrow_client
+            .get_history(&first_index, &1)
+            .get(0)
+            .unwrap()
+            .action,
+        AuditAction::MilestoneAdded
+    );
+    assert_eq!(
+        escrow_client
+            .get_history(&(history_count - 1), &1)
+            .get(0)
+            .unwrap()
+            .action,
+        AuditAction::Released
+    );
+    env.as_contract(&escrow_client.address, || {
+        assert!(!env
+            .storage()
+            .persistent()
+            .has(&DataKey::HistoryEntry(MAX_HISTORY_ENTRIES)));
+    });
+}
+
+#[test]
+fn test_ttl_is_extended_on_mutation_and_by_any_party() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let outsider_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: Address::generate(&env),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_ttl"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount: 100_000_000,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    let (instance_ttl, milestone_ttl) = env.as_contract(&escrow_client.address, || {
+        (
+            env.storage().instance().get_ttl(),
+            env.storage().persistent().get_ttl(&DataKey::Milestone(0)),
+        )
+    });
+    assert_eq!(instance_ttl, INSTANCE_EXTEND_TO);
+    assert_eq!(milestone_ttl, PERSISTENT_EXTEND_TO);
+
+    // Once the remaining TTL drops below the threshold, the next mutation bumps it back up
+    env.ledger()
+        .with_mut(|li| li.sequence_number += INSTANCE_EXTEND_TO - INSTANCE_TTL_THRESHOLD + 1);
+    escrow_client.change_milestone_status(
+        &0,
+        &String::from_str(&env, "Completed"),
+        &None,
+        &service_provider_address,
+    );
+    let (instance_ttl, milestone_ttl) = env.as_contract(&escrow_client.address, || {
+        (
+            env.storage().instance().get_ttl(),
+            env.storage().persistent().get_ttl(&DataKey::Milestone(0)),
+        )
+    });
+    assert_eq!(instance_ttl, INSTANCE_EXTEND_TO);
+    assert_eq!(milestone_ttl, PERSISTENT_EXTEND_TO);
+
+    // Any party can extend the footprint, not just the platform, one page at a time
+    let ledgers_to_extend = 200 * DAY_IN_LEDGERS;
+    let next_offset =
+        escrow_client.extend_contract_ttl(&outsider_address, &ledgers_to_extend, &0, &1);
+    assert_eq!(next_offset, Some(1));
+    let (instance_ttl, milestone_ttl, history_ttl) =
+        env.as_contract(&escrow_client.address, || {
+            (
+                env.storage().instance().get_ttl(),
+                env.storage().persistent().get_ttl(&DataKey::Milestone(0)),
+                env.storage()
+                    .persistent()
+                    .get_ttl(&DataKey::HistoryEntry(0)),
+            )
+        });
+    assert_eq!(instance_ttl, ledgers_to_extend);
+    assert_eq!(milestone_ttl, ledgers_to_extend);
+    assert!(history_ttl < ledgers_to_extend);
+
+    let mut next_offset = next_offset;
+    while let Some(offset) = next_offset {
+        next_offset =
+            escrow_client.extend_contract_ttl(&outsider_address, &ledgers_to_extend, &offset, &1);
+    }
+    let history_ttl = env.as_contract(&escrow_client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::HistoryEntry(0))
+    });
+    assert_eq!(history_ttl, ledgers_to_extend);
+}
+
+#[test]
+fn test_storage_layer_versioning_and_missing_keys() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+
+    let result = escrow_client.try_get_escrow();
+    assert_eq!(result.err(), Some(Ok(ContractError::EscrowNotFound)));
+
+    let roles: Roles = Roles {
+        approver: Address::generate(&env),
+        service_provider: service_provider_address.clone(),
+        platform_address: Address::generate(&env),
+        release_signer: Address::generate(&env),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_storage"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount: 100_000_000,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+
+    env.as_contract(&escrow_client.address, || {
+        assert_eq!(load_storage_version(&env), STORAGE_VERSION);
+        assert!(load_escrow(&env).unwrap() == escrow_properties);
+        assert!(load_milestone(&env, 0).unwrap() == milestones.get(0).unwrap());
+        assert_eq!(
+            load_milestone(&env, 1).err(),
+            Some(ContractError::InvalidMileStoneIndex)
+        );
+
+        // Simulate data left behind by a newer contract layout
+        save_instance(&env, &DataKey::StorageVersion, &(STORAGE_VERSION + 1));
+    });
+
+    let result = escrow_client.try_get_escrow();
+    assert_eq!(
+        result.err(),
+        Some(Ok(ContractError::UnsupportedStorageVersion))
+    );
+
+    // Older layouts have no migration path, and escrows predating the key read as version 0
+    env.as_contract(&escrow_client.address, || {
+        save_instance(&env, &DataKey::StorageVersion, &(STORAGE_VERSION - 1));
+    });
+    let result = escrow_client.try_get_escrow();
+    assert_eq!(
+        result.err(),
+        Some(Ok(ContractError::UnsupportedStorageVersion))
+    );
+
+    env.as_contract(&escrow_client.address, || {
+        env.storage().instance().remove(&DataKey::StorageVersion);
+        assert_eq!(load_storage_version(&env), 0);
+    });
+    let result = escrow_client.try_get_escrow();
+    assert_eq!(
+        result.err(),
+        Some(Ok(ContractError::UnsupportedStorageVersion))
+    );
+}
+
+#[test]
+fn test_get_multiple_escrow_balances_reports_per_address_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let service_provider = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let roles = Roles {
+        approver: Address::generate(&env),
+        service_provider: service_provider.clone(),
+        platform_address: Address::generate(&env),
+        release_signer: Address::generate(&env),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider.clone(),
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_properties = Escrow {
+        engagement_id: String::from_str(&env, "engagement_safe_reads"),
+        title: String::from_str(&env, "Escrow for safe reads"),
+        description: String::from_str(&env, "Test for fallible balance queries"),
+        roles,
+        amount: 50_000_000,
+        platform_fee: 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token
+        .1
+        .mint(&escrow_client.address, &escrow_properties.amount);
+
+    let uninitialized_client = create_escrow_contract(&env).client;
+    let unknown_address = Address::generate(&env);
+
+    let result = escrow_client.try_get_escrow_by_contract_id(&uninitialized_client.address);
+    assert_eq!(result.err(), Some(Ok(ContractError::EscrowNotFound)));
+
+    // The token contract exists but does not expose `get_escrow`
+    let result = escrow_client.try_get_escrow_by_contract_id(&usdc_token.0.address);
+    assert_eq!(result.err(), Some(Ok(ContractError::InvalidEscrowContract)));
+
+    let balances = escrow_client.get_multiple_escrow_balances(&vec![
+        &env,
+        escrow_client.address.clone(),
+        uninitialized_client.address.clone(),
+        usdc_token.0.address.clone(),
+        unknown_address.clone(),
+    ]);
+    assert_eq!(balances.len(), 4);
+
+    let funded = balances.get(0).unwrap();
+    assert_eq!(funded.balance, escrow_properties.amount);
+    assert_eq!(funded.trustline_decimals, 7);
+    assert_eq!(funded.error_code, None);
+
+    let uninitialized = balances.get(1).unwrap();
+    assert_eq!(uninitialized.address, uninitialized_client.address);
+    assert_eq!(uninitialized.balance, 0);
+    assert_eq!(
+        uninitialized.error_code,
+        Some(ContractError::EscrowNotFound as u32)
+    );
+
+    assert_eq!(
+        balances.get(2).unwrap().error_code,
+        Some(ContractError::InvalidEscrowContract as u32)
+    );
+    assert_eq!(
+        balances.get(3).unwrap().error_code,
+        Some(ContractError::InvalidEscrowContract as u32)
+    );
+}
+
+#[test]
+fn test_get_escrow_summaries_with_configurable_batch_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver = Address::generate(&env);
+    let service_provider = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let roles = Roles {
+        approver: approver.clone(),
+        service_provider: service_provider.clone(),
+        platform_address: Address::generate(&env),
+        release_signer: Address::generate(&env),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider.clone(),
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+        Milestone {
+            description: String::from_str(&env, "Second milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_properties = Escrow {
+        engagement_id: String::from_str(&env, "engagement_summary"),
+        title: String::from_str(&env, "Escrow for summaries"),
+        description: String::from_str(&env, "Test for dashboard summaries"),
+        roles,
+        amount: 40_000_000,
+        platform_fee: 250,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let test_data = create_escrow_contract(&env);
+    let c1 = test_data.client;
+    let contract_admin = test_data.admin;
+    c1.initialize_escrow(&escrow_properties, &milestones);
+    c1.approve_milestone(&0, &approver);
+    usdc_token.1.mint(&c1.address, &10_000_000);
+
+    let c2 = create_escrow_contract(&env).client;
+    c2.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&c2.address, &escrow_properties.amount);
+
+    let uninitialized = create_escrow_contract(&env).client;
+
+    let summaries = c1.get_escrow_summaries(&vec![
+        &env,
+        c1.address.clone(),
+        c2.address.clone(),
+        uninitialized.address.clone(),
+    ]);
+    assert_eq!(summaries.len(), 3);
+
+    match summaries.get(0).unwrap() {
+        EscrowSummaryEntry::Summary(summary) => {
+            assert_eq!(summary.address, c1.address);
+            assert_eq!(summary.total_funded, 10_000_000);
+            assert_eq!(summary.funding_percentage, 25);
+            assert_eq!(summary.milestone_count, 2);
+            assert_eq!(summary.approved_milestone_count, 1);
+            assert_eq!(summary.platform_fee, 250);
+            assert_eq!(summary.protocol_fee_bps, c1.get_protocol_fee());
+            assert_eq!(summary.roles.approver, approver);
+        }
+        EscrowSummaryEntry::Failed(..) => panic!("expected a summary for the local escrow"),
+    }
+
+    match summaries.get(1).unwrap() {
+        EscrowSummaryEntry::Summary(summary) => {
+            assert_eq!(summary.address, c2.address);
+            assert_eq!(summary.funding_percentage, 100);
+            assert_eq!(summary.approved_milestone_count, 0);
+        }
+        EscrowSummaryEntry::Failed(..) => panic!("expected a summary for the remote escrow"),
+    }
+
+    match summaries.get(2).unwrap() {
+        EscrowSummaryEntry::Failed(address, error_code) => {
+            assert_eq!(address, uninitialized.address);
+            assert_eq!(error_code, ContractError::EscrowNotFound as u32);
+        }
+        EscrowSummaryEntry::Summary(_) => panic!("expected an error for the uninitialized escrow"),
+    }
+
+    // The batch limit is configurable by the admin only, within the hard cap
+    assert_eq!(c1.get_max_batch_size(), 20);
+    let result = c1.try_set_max_batch_size(&approver, &2);
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    let result = c1.try_set_max_batch_size(&contract_admin, &500);
+    assert_eq!(result, Err(Ok(ContractError::InvalidMaxBatchSize)));
+    let result = c1.try_set_max_batch_size(&contract_admin, &0);
+    assert_eq!(result, Err(Ok(ContractError::InvalidMaxBatchSize)));
+
+    c1.set_max_batch_size(&contract_admin, &2);
+    assert_eq!(c1.get_max_batch_size(), 2);
+
+    let too_many = vec![
+        &env,
+        c1.address.clone(),
+        c2.address.clone(),
+        uninitialized.address.clone(),
+    ];
+    let result = c1.try_get_escrow_summaries(&too_many);
+    assert!(matches!(
+        result,
+        Err(Ok(ContractError::TooManyEscrowsRequested))
+    ));
+    let result = c1.try_get_multiple_escrow_balances(&too_many);
+    assert!(matches!(
+        result,
+        Err(Ok(ContractError::TooManyEscrowsRequested))
+    ));
+}
+
+#[test]
+fn test_get_status_follows_escrow_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: Address::generate(&env),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_status"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+        Milestone {
+            description: String::from_str(&env, "Second milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    assert_eq!(escrow_client.get_status(), EscrowStatus::AwaitingFunding);
+
+    usdc_token.1.mint(&escrow_client.address, &(amount / 2));
+    assert_eq!(escrow_client.get_status(), EscrowStatus::PartiallyFunded);
+
+    // Overfund slightly so something is left behind after the release
+    usdc_token
+        .1
+        .mint(&escrow_client.address, &(amount / 2 + 1_000));
+    assert_eq!(escrow_client.get_status(), EscrowStatus::Active);
+
+    escrow_client.approve_milestone(&0, &approver_address);
+    assert_eq!(escrow_client.get_status(), EscrowStatus::AwaitingApproval);
+
+    escrow_client.approve_milestone(&1, &approver_address);
+    assert_eq!(escrow_client.get_status(), EscrowStatus::ReadyToRelease);
+
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+    assert_eq!(escrow_client.get_status(), EscrowStatus::Released);
+
+    // A disputed escrow is closed once the resolution drains it
+    let disputed_client = create_escrow_contract(&env).client;
+    disputed_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&disputed_client.address, &amount);
+    disputed_client.dispute_escrow(&approver_address);
+    assert_eq!(disputed_client.get_status(), EscrowStatus::Disputed);
+
+    let mut distributions = Map::new(&env);
+    distributions.set(approver_address.clone(), amount);
+    disputed_client.resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &token_distributions(&env, &usdc_token.0.address, &distributions),
+    );
+    assert_eq!(disputed_client.get_status(), EscrowStatus::Closed);
+}
+
+#[test]
+fn test_preview_release_and_dispute_resolution_match_real_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_preview"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 5 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+
+    // The preview fails exactly like the real call would
+    let result = escrow_client.try_preview_release();
+    assert_eq!(result, Err(Ok(ContractError::EscrowNotCompleted)));
+
+    escrow_client.approve_milestone(&0, &approver_address);
+    let preview = escrow_client.preview_release().get(0).unwrap();
+    assert_eq!(preview.trustless_work_fee, 300_000);
+    assert_eq!(preview.platform_fee, 5_000_000);
+    assert_eq!(preview.payouts.len(), 1);
+    let payout = preview.payouts.get(0).unwrap();
+    assert_eq!(payout.recipient, service_provider_address);
+    assert_eq!(payout.amount, 94_700_000);
+
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+    assert_eq!(
+        usdc_token.0.balance(&trustless_work_address),
+        preview.trustless_work_fee
+    );
+    assert_eq!(
+        usdc_token.0.balance(&platform_address),
+        preview.platform_fee
+    );
+    assert_eq!(
+        usdc_token.0.balance(&service_provider_address),
+        payout.amount
+    );
+
+    let result = escrow_client.try_preview_release();
+    assert_eq!(result, Err(Ok(ContractError::EscrowAlreadyReleased)));
+
+    // Dispute resolution preview
+    let disputed_client = create_escrow_contract(&env).client;
+    disputed_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&disputed_client.address, &amount);
+
+    let mut distributions = Map::new(&env);
+    distributions.set(approver_address.clone(), 60_000_000);
+    distributions.set(service_provider_address.clone(), 40_000_000);
+
+    let result = disputed_client.try_preview_dispute_resolution(&token_distributions(
+        &env,
+        &usdc_token.0.address,
+        &distributions,
+    ));
+    assert_eq!(result, Err(Ok(ContractError::EscrowNotInDispute)));
+
+    disputed_client.dispute_escrow(&approver_address);
+
+    let mut short_distributions = Map::new(&env);
+    short_distributions.set(approver_address.clone(), 10_000_000);
+    let result = disputed_client.try_preview_dispute_resolution(&token_distributions(
+        &env,
+        &usdc_token.0.address,
+        &short_distributions,
+    ));
+    assert_eq!(
+        result,
+        Err(Ok(ContractError::DistributionsMustEqualEscrowBalance))
+    );
+
+    let preview = disputed_client
+        .preview_dispute_resolution(&token_distributions(
+            &env,
+            &usdc_token.0.address,
+            &distributions,
+        ))
+        .get(0)
+        .unwrap();
+    assert_eq!(preview.trustless_work_fee, 300_000);
+    assert_eq!(preview.platform_fee, 5_000_000);
+    assert_eq!(preview.payouts.len(), 2);
+
+    let approver_before = usdc_token.0.balance(&approver_address);
+    let provider_before = usdc_token.0.balance(&service_provider_address);
+    disputed_client.resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &token_distributions(&env, &usdc_token.0.address, &distributions),
+    );
+
+    for payout in preview.payouts.iter() {
+        let before = if payout.recipient == approver_address {
+            approver_before
+        } else {
+            provider_before
+        };
+        assert_eq!(
+            usdc_token.0.balance(&payout.recipient) - before,
+            payout.amount
+        );
+    }
+    assert_eq!(usdc_token.0.balance(&disputed_client.address), 0);
+}
+
+#[test]
+fn test_audit_history_records_every_action() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 100;
+        li.timestamp = 1_700_000_000;
+    });
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+    usdc_token.1.mint(&approver_address, &amount);
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_history"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    escrow_client.fund_escrow(
+        &approver_address,
+        &escrow_properties,
+        &usdc_token.0.address,
+        &amount,
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 200;
+        li.timestamp = 1_700_000_500;
+    });
+    escrow_client.change_milestone_status(
+        &0,
+        &String::from_str(&env, "Completed"),
+        &None,
+        &service_provider_address,
+    );
+    escrow_client.approve_milestone(&0, &approver_address);
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+
+    // Failed calls roll back and leave no trace
+    let result = escrow_client.try_release_funds(&release_signer_address, &trustless_work_address);
+    assert_eq!(result, Err(Ok(ContractError::EscrowAlreadyReleased)));
+
+    assert_eq!(escrow_client.get_history_count(), 5);
+    let history = escrow_client.get_history(&0, &10);
+    let actions: std::vec::Vec<(AuditAction, Address, i128)> = history
+        .iter()
+        .map(|entry| (entry.action, entry.actor, entry.amount))
+        .collect();
+    assert_eq!(
+        actions,
+        std::vec![
+            (AuditAction::Initialized, platform_address.clone(), amount),
+            (AuditAction::Funded, approver_address.clone(), amount),
+            (
+                AuditAction::MilestoneStatusChanged,
+                service_provider_address.clone(),
+                0
+            ),
+            (AuditAction::MilestoneApproved, approver_address.clone(), 0),
+            (
+                AuditAction::Released,
+                release_signer_address.clone(),
+                amount
+            ),
+        ]
+    );
+    // Entries that move tokens name the token; the others carry none
+    let tokens: std::vec::Vec<Option<Address>> = history.iter().map(|entry| entry.token).collect();
+    let usdc = Some(usdc_token.0.address.clone());
+    assert_eq!(
+        tokens,
+        std::vec![usdc.clone(), usdc.clone(), None, None, usdc]
+    );
+
+    let first = history.get(0).unwrap();
+    assert_eq!(first.ledger, 100);
+    assert_eq!(first.timestamp, 1_700_000_000);
+    let last = history.get(4).unwrap();
+    assert_eq!(last.ledger, 200);
+    assert_eq!(last.timestamp, 1_700_000_500);
+
+    let page = escrow_client.get_history(&3, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().action, AuditAction::MilestoneApproved);
+    assert_eq!(escrow_client.get_history(&5, &10).len(), 0);
+}
+
+#[test]
+fn test_payout_events_carry_fee_breakdown() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: dispute_resolver_address.clone(),
+        receiver: service_provider_address.clone(),
+    };
+
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_payout_events"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 5 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+    escrow_client.approve_milestone(&0, &approver_address);
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+
+    // Event data is encoded with the fields sorted by name
+    let data = find_event_data(&env, "tw_rel_payout");
+    let field = |index: u32| data.get(index).unwrap();
+    assert_eq!(
+        soroban_sdk::Vec::<Payout>::try_from_val(&env, &field(0)).unwrap(),
+        vec![
+            &env,
+            Payout {
+                recipient: service_provider_address.clone(),
+                amount: 94_700_000,
+            }
+        ]
+    );
+    assert_eq!(
+        Address::try_from_val(&env, &field(1)).unwrap(),
+        platform_address
+    );
+    assert_eq!(i128::try_from_val(&env, &field(2)).unwrap(), 5_000_000);
+    assert_eq!(
+        Address::try_from_val(&env, &field(3)).unwrap(),
+        usdc_token.0.address
+    );
+    assert_eq!(
+        Address::try_from_val(&env, &field(4)).unwrap(),
+        trustless_work_address
+    );
+    assert_eq!(i128::try_from_val(&env, &field(5)).unwrap(), 300_000);
+
+    let disputed_client = create_escrow_contract(&env).client;
+    disputed_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&disputed_client.address, &amount);
+    disputed_client.dispute_escrow(&approver_address);
+
+    let mut distributions = Map::new(&env);
+    distributions.set(approver_address.clone(), 60_000_000);
+    distributions.set(service_provider_address.clone(), 40_000_000);
+    let preview = disputed_client
+        .preview_dispute_resolution(&token_distributions(
+            &env,
+            &usdc_token.0.address,
+            &distributions,
+        ))
+        .get(0)
+        .unwrap();
+    disputed_client.resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &token_distributions(&env, &usdc_token.0.address, &distributions),
+    );
+
+    let data = find_event_data(&env, "tw_dis_payout");
+    let payouts = soroban_sdk::Vec::<Payout>::try_from_val(&env, &data.get(0).unwrap()).unwrap();
+    assert_eq!(payouts, preview.payouts);
+    assert_eq!(
+        i128::try_from_val(&env, &data.get(2).unwrap()).unwrap(),
+        preview.platform_fee
+    );
+    assert_eq!(
+        i128::try_from_val(&env, &data.get(5).unwrap()).unwrap(),
+        preview.trustless_work_fee
+    );
+}
+
+#[test]
+fn test_events_use_versioned_slim_schema() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+
+    let roles: Roles = Roles {
+        approver: Address::generate(&env),
+        service_provider: service_provider_address.clone(),
+        platform_address: Address::generate(&env),
+        release_signer: Address::generate(&env),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let engagement_id = String::from_str(&env, "test_event_schema");
+    let escrow_properties: Escrow = Escrow {
+        engagement_id: engagement_id.clone(),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount: 100_000_000,
+        platform_fee: 3 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    escrow_client.change_milestone_status(
+        &0,
+        &String::from_str(&env, "Completed"),
+        &None,
+        &service_provider_address,
+    );
+
+    let (_, topics, _) = env.events().all().last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(),
+        Symbol::new(&env, "tw_ms_change")
+    );
+    assert_eq!(
+        Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(),
+        Symbol::new(&env, "v2")
+    );
+    // The engagement and the acting party are indexed as topics
+    assert_eq!(
+        String::try_from_val(&env, &topics.get(2).unwrap()).unwrap(),
+        engagement_id
+    );
+    assert_eq!(
+        Address::try_from_val(&env, &topics.get(3).unwrap()).unwrap(),
+        service_provider_address
+    );
+
+    // Only the diff is published, with the fields sorted by name
+    let data = find_event_data(&env, "tw_ms_change");
+    assert_eq!(data.len(), 3);
+    let field = |index: u32| data.get(index).unwrap();
+    assert_eq!(u32::try_from_val(&env, &field(0)).unwrap(), 0);
+    assert_eq!(
+        String::try_from_val(&env, &field(1)).unwrap(),
+        String::from_str(&env, "Completed")
+    );
+    assert_eq!(
+        String::try_from_val(&env, &field(2)).unwrap(),
+        String::from_str(&env, "Pending")
+    );
+}
+
+#[test]
+fn test_release_splits_receiver_amount_between_payees() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+    let lead_address = Address::generate(&env);
+    let designer_address = Address::generate(&env);
+    let reviewer_address = Address::generate(&env);
+
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 1_000_003;
+
+    let roles: Roles = Roles {
+        approver: approver_address.clone(),
+        service_provider: service_provider_address.clone(),
+        platform_address: platform_address.clone(),
+        release_signer: release_signer_address.clone(),
+        dispute_resolver: Address::generate(&env),
+        receiver: service_provider_address.clone(),
+    };
+
+    let payee = |address: &Address, bps: u32| Payee {
+        address: address.clone(),
+        bps,
+    };
+    let mut escrow_properties: Escrow = Escrow {
+        engagement_id: String::from_str(&env, "test_payee_split"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles,
+        amount,
+        platform_fee: 5 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![
+            &env,
+            payee(&lead_address, 7000),
+            payee(&designer_address, 2000),
+            payee(&reviewer_address, 1000),
+        ],
+        extra_legs: vec![&env],
+    };
+
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    // Shares must be positive, unique and add up to exactly 10000 bps
+    let invalid_splits = [
+        vec![
+            &env,
+            payee(&lead_address, 7000),
+            payee(&designer_address, 2999),
+        ],
+        vec![&env, payee(&lead_address, 5000), payee(&lead_address, 5000)],
+        vec![
+            &env,
+            payee(&lead_address, 10000),
+            payee(&designer_address, 0),
+        ],
+    ];
+    for payees in invalid_splits {
+        let mut invalid_properties = escrow_properties.clone();
+        invalid_properties.payees = payees;
+        let escrow_client = create_escrow_contract(&env).client;
+        assert_eq!(
+            escrow_client
+                .try_initialize_escrow(&invalid_properties, &milestones)
+                .err(),
+            Some(Ok(ContractError::InvalidPayeeSplit))
+        );
+    }
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    usdc_token.1.mint(&escrow_client.address, &amount);
+    escrow_client.approve_milestone(&0, &approver_address);
+
+    // 30 bps protocol and 500 bps platform fees leave 947_003 for the payees. The rounded
+    // down shares add up to 947_002 and the leftover unit goes to the first payee.
+    let expected_payouts = vec![
+        &env,
+        Payout {
+            recipient: lead_address.clone(),
+            amount: 662_903,
+        },
+        Payout {
+            recipient: designer_address.clone(),
+            amount: 189_400,
+        },
+        Payout {
+            recipient: reviewer_address.clone(),
+            amount: 94_700,
+        },
+    ];
+    assert_eq!(
+        escrow_client.preview_release().get(0).unwrap().payouts,
+        expected_payouts
+    );
+
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+
+    let data = find_event_data(&env, "tw_rel_payout");
+    assert_eq!(
+        soroban_sdk::Vec::<Payout>::try_from_val(&env, &data.get(0).unwrap()).unwrap(),
+        expected_payouts
+    );
+    for payout in expected_payouts.iter() {
+        assert_eq!(usdc_token.0.balance(&payout.recipient), payout.amount);
+    }
+    assert_eq!(usdc_token.0.balance(&service_provider_address), 0);
+    assert_eq!(usdc_token.0.balance(&escrow_client.address), 0);
+
+    // Changing the split needs the consent of the current payees
+    escrow_properties.payees = vec![&env, payee(&lead_address, 10000)];
+    let split_client = create_escrow_contract(&env).client;
+    let mut initial_properties = escrow_properties.clone();
+    initial_properties.payees = vec![
+        &env,
+        payee(&lead_address, 5000),
+        payee(&designer_address, 5000),
+    ];
+    split_client.initialize_escrow(&initial_properties, &milestones);
+    let proposal = split_client
+        .update_escrow(&platform_address, &escrow_properties)
+        .unwrap();
+    assert!(proposal.required_approvals.contains(&designer_address));
+    assert!(proposal.required_approvals.contains(&lead_address));
+}
+
+#[test]
+fn test_fee_mode_sets_required_funding_and_receiver_amount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    // 30 bps protocol and 500 bps platform fees add up to 5_300_000
+    let cases = [
+        (FeeMode::DeductFromReceiver, 100_000_000, 94_700_000),
+        (FeeMode::ChargeFunder, 105_300_000, 100_000_000),
+        (FeeMode::Split, 102_650_000, 97_350_000),
+    ];
+
+    for (fee_mode, required_funding, receiver_amount) in cases {
+        let funder_address = Address::generate(&env);
+        let approver_address = Address::generate(&env);
+        let service_provider_address = Address::generate(&env);
+        let release_signer_address = Address::generate(&env);
+        let trustless_work_address = Address::generate(&env);
+
+        let escrow_properties: Escrow = Escrow {
+            engagement_id: String::from_str(&env, "test_fee_mode"),
+            title: String::from_str(&env, "Test Escrow"),
+            description: String::from_str(&env, "Test Escrow Description"),
+            roles: Roles {
+                approver: approver_address.clone(),
+                service_provider: service_provider_address.clone(),
+                platform_address: Address::generate(&env),
+                release_signer: release_signer_address.clone(),
+                dispute_resolver: Address::generate(&env),
+                receiver: service_provider_address.clone(),
+            },
+            amount,
+            platform_fee: 5 * 100,
+            fee_mode,
+            flags: Flags {
+                disputed: false,
+                released: false,
+                resolved: false,
+            },
+            trustline: token_trustline(&usdc_token.0),
+            fee_schedule: FeeSchedule::Flat,
+            receiver_memo: 0,
+            payees: vec![&env],
+            extra_legs: vec![&env],
+        };
+
+        let milestones = vec![
+            &env,
+            Milestone {
+                description: String::from_str(&env, "First milestone"),
+                status: String::from_str(&env, "Completed"),
+                evidence: String::from_str(&env, "Initial evidence"),
+                approved: false,
+            },
+        ];
+
+        let escrow_client = create_escrow_contract(&env).client;
+        let escrow = escrow_client.initialize_escrow(&escrow_properties, &milestones);
+        escrow_client.approve_milestone(&0, &approver_address);
+        assert_eq!(
+            escrow_client
+                .get_required_funding()
+                .get(0)
+                .unwrap()
+                .required_funding,
+            required_funding
+        );
+
+        // Depositing only the amount is not enough when fees are charged on top
+        usdc_token.1.mint(&funder_address, &required_funding);
+        escrow_client.fund_escrow(&funder_address, &escrow, &usdc_token.0.address, &amount);
+        if required_funding > amount {
+            assert_eq!(escrow_client.get_status(), EscrowStatus::PartiallyFunded);
+            assert_eq!(
+                escrow_client
+                    .try_release_funds(&release_signer_address, &trustless_work_address)
+                    .err(),
+                Some(Ok(ContractError::EscrowBalanceNotEnoughToSendEarnings))
+            );
+            escrow_client.fund_escrow(
+                &funder_address,
+                &escrow,
+                &usdc_token.0.address,
+                &(required_funding - amount),
+            );
+        }
+
+        assert_eq!(escrow_client.get_status(), EscrowStatus::ReadyToRelease);
+        let summary = escrow_client.get_escrow_summary();
+        assert_eq!(summary.required_funding, required_funding);
+        assert_eq!(summary.funding_percentage, 100);
+
+        let preview = escrow_client.preview_release().get(0).unwrap();
+        assert_eq!(preview.trustless_work_fee, 300_000);
+        assert_eq!(preview.platform_fee, 5_000_000);
+        assert_eq!(preview.payouts.get(0).unwrap().amount, receiver_amount);
+
+        escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+        assert_eq!(
+            usdc_token.0.balance(&service_provider_address),
+            receiver_amount
+        );
+        assert_eq!(usdc_token.0.balance(&trustless_work_address), 300_000);
+        assert_eq!(usdc_token.0.balance(&escrow_client.address), 0);
+    }
+}
+
+#[test]
+fn test_dispute_resolution_charges_fees_regardless_of_fee_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+    // What a ChargeFunder escrow holds once funded: the amount plus 5_300_000 of fees
+    let balance: i128 = 105_300_000;
+
+    for fee_mode in [
+        FeeMode::DeductFromReceiver,
+        FeeMode::ChargeFunder,
+        FeeMode::Split,
+    ] {
+        let approver_address = Address::generate(&env);
+        let service_provider_address = Address::generate(&env);
+        let dispute_resolver_address = Address::generate(&env);
+        let trustless_work_address = Address::generate(&env);
+
+        let escrow_properties: Escrow = Escrow {
+            engagement_id: String::from_str(&env, "test_dispute_fee_mode"),
+            title: String::from_str(&env, "Test Escrow"),
+            description: String::from_str(&env, "Test Escrow Description"),
+            roles: Roles {
+                approver: approver_address.clone(),
+                service_provider: service_provider_address.clone(),
+                platform_address: Address::generate(&env),
+                release_signer: Address::generate(&env),
+                dispute_resolver: dispute_resolver_address.clone(),
+                receiver: service_provider_address.clone(),
+            },
+            amount,
+            platform_fee: 5 * 100,
+            fee_mode,
+            flags: Flags {
+                disputed: false,
+                released: false,
+                resolved: false,
+            },
+            trustline: token_trustline(&usdc_token.0),
+            fee_schedule: FeeSchedule::Flat,
+            receiver_memo: 0,
+            payees: vec![&env],
+            extra_legs: vec![&env],
+        };
+
+        let milestones = vec![
+            &env,
+            Milestone {
+                description: String::from_str(&env, "First milestone"),
+                status: String::from_str(&env, "Completed"),
+                evidence: String::from_str(&env, "Initial evidence"),
+                approved: false,
+            },
+        ];
+
+        let escrow_client = create_escrow_contract(&env).client;
+        escrow_client.initialize_escrow(&escrow_properties, &milestones);
+        usdc_token.1.mint(&escrow_client.address, &balance);
+        escrow_client.dispute_escrow(&approver_address);
+
+        // The fee mode only shapes releases: a resolution charges the standard fees on the
+        // distributed total and every recipient bears them pro rata
+        let mut distributions = Map::new(&env);
+        distributions.set(approver_address.clone(), balance / 2);
+        distributions.set(service_provider_address.clone(), balance / 2);
+        escrow_client.resolve_dispute(
+            &dispute_resolver_address,
+            &trustless_work_address,
+            &token_distributions(&env, &usdc_token.0.address, &distributions),
+        );
+
+        assert_eq!(usdc_token.0.balance(&trustless_work_address), 315_900);
+        assert_eq!(usdc_token.0.balance(&approver_address), 49_859_550);
+        assert_eq!(usdc_token.0.balance(&service_provider_address), 49_859_550);
+        assert_eq!(usdc_token.0.balance(&escrow_client.address), 0);
+    }
+}
+
+#[test]
+fn test_fee_strategies_at_tier_and_bound_edges() {
+    let env = Env::default();
+
+    // Tiers are deliberately out of order; each amount uses the narrowest covering band
+    let tiered = TieredFeeCalculator {
+        tiers: vec![
+            &env,
+            FeeTier {
+                up_to: 10_000,
+                bps: 300,
+            },
+            FeeTier {
+                up_to: 1_000,
+                bps: 500,
+            },
+            FeeTier {
+                up_to: 100_000,
+                bps: 100,
+            },
+        ],
+    };
+    let tier_cases = [
+        (1, 0),
+        (1_000, 50),
+        (1_001, 30),
+        (10_000, 300),
+        (10_001, 100),
+        (100_000, 1_000),
+        // Beyond the last band the widest tier still applies
+        (1_000_000, 10_000),
+    ];
+    for (amount, platform_fee) in tier_cases {
+        assert_eq!(tiered.calculate_platform_fee(amount), Ok(platform_fee));
+    }
+
+    let no_tiers = TieredFeeCalculator { tiers: vec![&env] };
+    assert_eq!(no_tiers.calculate_platform_fee(1_000_000), Ok(0));
+
+    let bounded = BoundedFeeCalculator {
+        platform_fee_bps: 100,
+        bounds: FeeBounds {
+            min_fee: 50,
+            max_fee: 500,
+        },
+    };
+    let bound_cases = [
+        (1_000, 50),
+        (5_000, 50),
+        (5_100, 51),
+        (20_000, 200),
+        (50_000, 500),
+        (50_100, 500),
+    ];
+    for (amount, platform_fee) in bound_cases {
+        assert_eq!(bounded.calculate_platform_fee(amount), Ok(platform_fee));
+    }
+
+    // The protocol cap applies on top of every strategy
+    let flat = FlatFeeCalculator {
+        platform_fee_bps: 9_000,
+    };
+    let fees = flat.calculate_standard_fees(10_000, 30, 2_000).unwrap();
+    assert_eq!(fees.trustless_work_fee, 30);
+    assert_eq!(fees.platform_fee, 2_000);
+    assert_eq!(fees.receiver_amount, 7_970);
+
+    let fees = bounded.calculate_standard_fees(1_000, 30, 200).unwrap();
+    assert_eq!(fees.platform_fee, 20);
+    assert_eq!(fees.receiver_amount, 977);
+
+    let fees = tiered.calculate_standard_fees(10_000, 30, 9_900).unwrap();
+    assert_eq!(fees.trustless_work_fee, 30);
+    assert_eq!(fees.platform_fee, 300);
+    assert_eq!(fees.receiver_amount, 9_670);
+}
+
+#[test]
+fn test_fee_schedule_is_selected_per_escrow_and_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let funder_address = Address::generate(&env);
+
+    let escrow_properties = |fee_schedule: FeeSchedule, platform_fee: u32| Escrow {
+        roles: Roles {
+            approver: approver_address.clone(),
+            service_provider: service_provider_address.clone(),
+            release_signer: release_signer_address.clone(),
+            receiver: service_provider_address.clone(),
+            ..generate_roles(&env)
+        },
+        amount,
+        platform_fee,
+        fee_schedule,
+        ..default_escrow(&env, token_trustline(&usdc_token.0))
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+    let tiers = |env: &Env, top_bps: u32| {
+        vec![
+            env,
+            FeeTier {
+                up_to: 10_000_000,
+                bps: 500,
+            },
+            FeeTier {
+                up_to: 100_000_000,
+                bps: top_bps,
+            },
+        ]
+    };
+
+    let invalid_schedules = [
+        (
+            FeeSchedule::Tiered(vec![&env, FeeTier { up_to: 0, bps: 100 }]),
+            ContractError::InvalidFeeTier,
+        ),
+        (
+            FeeSchedule::Tiered(vec![
+                &env,
+                FeeTier {
+                    up_to: 10_000_000,
+                    bps: 100,
+                },
+                FeeTier {
+                    up_to: 10_000_000,
+                    bps: 200,
+                },
+            ]),
+            ContractError::InvalidFeeTier,
+        ),
+        (
+            FeeSchedule::Tiered(tiers(&env, 9_901)),
+            ContractError::PlatformFeeTooHigh,
+        ),
+        (
+            FeeSchedule::Bounded(token_fee_bounds(&env, &usdc_token.0.address, 600, 500)),
+            ContractError::InvalidFeeBounds,
+        ),
+        (
+            FeeSchedule::Bounded(token_fee_bounds(&env, &usdc_token.0.address, -1, 500)),
+            ContractError::InvalidFeeBounds,
+        ),
+        // Bounds are in token units, so each leg needs its own and no other token may have any
+        (
+            FeeSchedule::Bounded(Map::new(&env)),
+            ContractError::InvalidFeeBounds,
+        ),
+        (
+            FeeSchedule::Bounded(token_fee_bounds(&env, &Address::generate(&env), 0, 500)),
+            ContractError::InvalidFeeBounds,
+        ),
+    ];
+    for (fee_schedule, error) in invalid_schedules {
+        let escrow_client = create_escrow_contract(&env).client;
+        assert_eq!(
+            escrow_client
+                .try_initialize_escrow(&escrow_properties(fee_schedule, 0), &milestones)
+                .err(),
+            Some(Ok(error))
+        );
+    }
+
+    // An amount right at the top of a band pays that band's rate
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    let escrow = escrow_client.initialize_escrow(
+        &escrow_properties(FeeSchedule::Tiered(tiers(&env, 200)), 0),
+        &milestones,
+    );
+    escrow_client.approve_milestone(&0, &approver_address);
+    usdc_token.1.mint(&funder_address, &amount);
+    escrow_client.fund_escrow(&funder_address, &escrow, &usdc_token.0.address, &amount);
+
+    let preview = escrow_client.preview_release().get(0).unwrap();
+    assert_eq!(preview.trustless_work_fee, 300_000);
+    assert_eq!(preview.platform_fee, 2_000_000);
+    assert_eq!(preview.payouts.get(0).unwrap().amount, 97_700_000);
+
+    // Only the admin manages the protocol cap, and never above 99%
+    assert_eq!(escrow_client.get_max_platform_fee_bps(), 9_900);
+    assert_eq!(
+        escrow_client
+            .try_set_max_platform_fee_bps(&approver_address, &100)
+            .err(),
+        Some(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_max_platform_fee_bps(&test_data.admin, &9_901)
+            .err(),
+        Some(Ok(ContractError::PlatformFeeTooHigh))
+    );
+
+    // Lowering the cap limits releases of escrows already configured above it
+    escrow_client.set_max_platform_fee_bps(&test_data.admin, &100);
+    let data = find_event_data(&env, "tw_plat_cap");
+    assert_eq!(u32::try_from_val(&env, &data.get(0).unwrap()).unwrap(), 100);
+    assert_eq!(escrow_client.get_max_platform_fee_bps(), 100);
+    let preview = escrow_client.preview_release().get(0).unwrap();
+    assert_eq!(preview.platform_fee, 1_000_000);
+    assert_eq!(preview.payouts.get(0).unwrap().amount, 98_700_000);
+
+    escrow_client.release_funds(&release_signer_address, &Address::generate(&env));
+    assert_eq!(usdc_token.0.balance(&service_provider_address), 98_700_000);
+}
+
+#[test]
+fn test_partner_platform_protocol_fee_discount() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let amount: i128 = 100_000_000;
+
+    let approver_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+    let funder_address = Address::generate(&env);
+
+    let escrow_properties = Escrow {
+        engagement_id: String::from_str(&env, "test_fee_discount"),
+        title: String::from_str(&env, "Test Escrow"),
+        description: String::from_str(&env, "Test Escrow Description"),
+        roles: Roles {
+            approver: approver_address.clone(),
+            service_provider: service_provider_address.clone(),
+            platform_address: platform_address.clone(),
+            release_signer: release_signer_address.clone(),
+            dispute_resolver: Address::generate(&env),
+            receiver: service_provider_address.clone(),
+        },
+        amount,
+        platform_fee: 5 * 100,
+        fee_mode: FeeMode::DeductFromReceiver,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: token_trustline(&usdc_token.0),
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![&env],
+        extra_legs: vec![&env],
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Completed"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let governance = create_escrow_contract(&env);
+    let governance_client = governance.client;
+    let contract_admin = governance.admin;
+    let escrow_client = create_governed_escrow_contract(&env, &governance_client.address);
+    let escrow = escrow_client.initialize_escrow(&escrow_properties, &milestones);
+    escrow_client.approve_milestone(&0, &approver_address);
+    usdc_token.1.mint(&funder_address, &amount);
+    escrow_client.fund_escrow(&funder_address, &escrow, &usdc_token.0.address, &amount);
+
+    assert!(escrow_client.get_platform_fee_discounts().is_empty());
+    assert_eq!(
+        governance_client
+            .try_set_platform_fee_discount(&approver_address, &platform_address, &10)
+            .err(),
+        Some(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    assert_eq!(
+        governance_client
+            .try_set_platform_fee_discount(&contract_admin, &platform_address, &101)
+            .err(),
+        Some(Ok(ContractError::ProtocolFeeTooHigh))
+    );
+    // Discounts are kept on the governance contract, not on each escrow
+    assert_eq!(
+        escrow_client
+            .try_set_platform_fee_discount(&contract_admin, &platform_address, &10)
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+
+    governance_client.set_platform_fee_discount(&contract_admin, &platform_address, &10);
+    let data = find_event_data(&env, "tw_fee_disc");
+    assert!(bool::try_from_val(&env, &data.get(0).unwrap()).unwrap());
+    assert_eq!(u32::try_from_val(&env, &data.get(1).unwrap()).unwrap(), 10);
+    assert_eq!(
+        escrow_client.get_platform_fee_discounts(),
+        Map::from_array(&env, [(platform_address.clone(), 10)])
+    );
+    assert_eq!(
+        escrow_client.get_platform_protocol_fee(&platform_address),
+        10
+    );
+    assert_eq!(
+        escrow_client.get_platform_protocol_fee(&Address::generate(&env)),
+        30
+    );
+    assert_eq!(escrow_client.get_escrow_summary().protocol_fee_bps, 10);
+
+    // A discount never exceeds the regular protocol fee
+    governance_client.set_protocol_fee(&contract_admin, &5);
+    assert_eq!(
+        escrow_client.get_platform_protocol_fee(&platform_address),
+        5
+    );
+    governance_client.set_protocol_fee(&contract_admin, &30);
+
+    let preview = escrow_client.preview_release().get(0).unwrap();
+    assert_eq!(preview.trustless_work_fee, 100_000);
+    assert_eq!(preview.platform_fee, 5_000_000);
+    assert_eq!(preview.payouts.get(0).unwrap().amount, 94_900_000);
+
+    governance_client.remove_platform_fee_discount(&contract_admin, &platform_address);
+    let data = find_event_data(&env, "tw_fee_disc");
+    assert!(!bool::try_from_val(&env, &data.get(0).unwrap()).unwrap());
+    assert_eq!(u32::try_from_val(&env, &data.get(1).unwrap()).unwrap(), 30);
+    assert!(escrow_client.get_platform_fee_discounts().is_empty());
+    assert_eq!(
+        escrow_client
+            .preview_release()
+            .get(0)
+            .unwrap()
+            .trustless_work_fee,
+        300_000
+    );
+
+    governance_client.set_platform_fee_discount(&contract_admin, &platform_address, &10);
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+    assert_eq!(usdc_token.0.balance(&trustless_work_address), 100_000);
+    assert_eq!(usdc_token.0.balance(&service_provider_address), 94_900_000);
+}
+
+#[test]
+fn test_fees_always_leave_receiver_minimum_share() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let platform_address = Address::generate(&env);
+    let service_provider_address = Address::generate(&env);
+
+    let escrow_properties = |amount: i128, platform_fee: u32, fee_schedule: FeeSchedule| Escrow {
+        roles: Roles {
+            service_provider: service_provider_address.clone(),
+            platform_address: platform_address.clone(),
+            receiver: service_provider_address.clone(),
+            ..generate_roles(&env)
+        },
+        amount,
+        platform_fee,
+        fee_schedule,
+        ..default_escrow(&env, token_trustline(&usdc_token.0))
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+    let bounded = |min_fee: i128| {
+        FeeSchedule::Bounded(token_fee_bounds(
+            &env,
+            &usdc_token.0.address,
+            min_fee,
+            1_000,
+        ))
+    };
+
+    // The calculator never produces an empty payout
+    let flat = FlatFeeCalculator {
+        platform_fee_bps: 9_970,
+    };
+    assert_eq!(
+        flat.calculate_standard_fees(10_000, 30, 10_000).err(),
+        Some(ContractError::PlatformFeeTooHigh)
+    );
+
+    let test_data = create_escrow_contract(&env);
+    let escrow_client = test_data.client;
+    let contract_admin = test_data.admin;
+
+    // 9900 platform + 30 protocol bps leave the default 50 bps minimum share
+    assert_eq!(escrow_client.get_min_receiver_share_bps(), 50);
+    escrow_client.set_protocol_fee(&contract_admin, &100);
+    assert_eq!(
+        escrow_client
+            .try_initialize_escrow(
+                &escrow_properties(1_000_000, 9_900, FeeSchedule::Flat),
+                &milestones
+            )
+            .err(),
+        Some(Ok(ContractError::PlatformFeeTooHigh))
+    );
+    escrow_client.set_protocol_fee(&contract_admin, &30);
+
+    assert_eq!(
+        escrow_client
+            .try_set_min_receiver_share_bps(&platform_address, &1_000)
+            .err(),
+        Some(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_min_receiver_share_bps(&contract_admin, &0)
+            .err(),
+        Some(Ok(ContractError::InvalidMinReceiverShare))
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_min_receiver_share_bps(&contract_admin, &9_901)
+            .err(),
+        Some(Ok(ContractError::InvalidMinReceiverShare))
+    );
+    escrow_client.set_min_receiver_share_bps(&contract_admin, &1_000);
+    let data = find_event_data(&env, "tw_min_share");
+    assert_eq!(
+        u32::try_from_val(&env, &data.get(0).unwrap()).unwrap(),
+        1_000
+    );
+
+    assert_eq!(
+        escrow_client
+            .try_initialize_escrow(
+                &escrow_properties(1_000_000, 8_971, FeeSchedule::Flat),
+                &milestones
+            )
+            .err(),
+        Some(Ok(ContractError::PlatformFeeTooHigh))
+    );
+
+    // An absolute minimum fee counts against the escrow's own amount: 3 + 897 leave 100
+    assert_eq!(
+        escrow_client
+            .try_initialize_escrow(&escrow_properties(1_000, 100, bounded(898)), &milestones)
+            .err(),
+        Some(Ok(ContractError::PlatformFeeTooHigh))
+    );
+    escrow_client.initialize_escrow(&escrow_properties(1_000, 100, bounded(897)), &milestones);
+
+    // Lowering the amount or raising the fee later is checked the same way
+    assert_eq!(
+        escrow_client
+            .try_set_amount(&platform_address, &usdc_token.0.address, &990)
+            .err(),
+        Some(Ok(ContractError::PlatformFeeTooHigh))
+    );
+    assert_eq!(
+        escrow_client
+            .try_update_escrow(
+                &platform_address,
+                &escrow_properties(1_000_000, 9_000, FeeSchedule::Flat)
+            )
+            .err(),
+        Some(Ok(ContractError::PlatformFeeTooHigh))
+    );
+    escrow_client.set_amount(&platform_address, &usdc_token.0.address, &2_000);
+}
+
+/// Token answering only the metadata part of the interface, with configurable decimals.
+#[contract]
+struct MetadataToken;
+
+#[contractimpl]
+impl MetadataToken {
+    pub fn __constructor(e: Env, decimals: u32, symbol: String) {
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "decimals"), &decimals);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "symbol"), &symbol);
+    }
+
+    pub fn decimals(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "decimals"))
+            .unwrap()
+    }
+
+    pub fn symbol(e: Env) -> String {
+        e.storage()
+            .instance()
+            .get(&Symbol::new(&e, "symbol"))
+            .unwrap()
+    }
+}
+
+#[test]
+fn test_initialize_validates_trustline_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let native_address = env
+        .deployer()
+        .with_stellar_asset(Bytes::from_array(&env, &[0, 0, 0, 0]))
+        .deploy();
+    let native_token = TokenClient::new(&env, &native_address);
+    let six_decimals_address =
+        env.register(MetadataToken, (6_u32, String::from_str(&env, "USDC6")));
+    let too_precise_address = env.register(MetadataToken, (19_u32, String::from_str(&env, "DUST")));
+
+    let escrow_properties = |trustline: Trustline, amount: i128| Escrow {
+        amount,
+        ..default_escrow(&env, trustline)
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+    let trustline = |address: &Address, decimals: u32, symbol: &str| Trustline {
+        address: address.clone(),
+        decimals,
+        symbol: String::from_str(&env, symbol),
+    };
+
+    // Native XLM and issued assets both report 7 decimals through their SAC
+    assert_eq!(native_token.decimals(), 7);
+    assert_eq!(native_token.symbol(), String::from_str(&env, "native"));
+    let valid_trustlines = [
+        trustline(&native_address, 7, "native"),
+        token_trustline(&usdc_token.0),
+        trustline(&six_decimals_address, 6, "USDC6"),
+    ];
+    for valid_trustline in valid_trustlines {
+        let escrow_client = create_escrow_contract(&env).client;
+        let escrow = escrow_client.initialize_escrow(
+            &escrow_properties(valid_trustline.clone(), 1_000_000),
+            &milestones,
+        );
+        assert!(escrow.trustline == valid_trustline);
+        assert!(escrow_client.get_escrow().trustline == valid_trustline);
+    }
+
+    // Decimals and symbol come from the token, whatever the caller passes
+    let escrow_client = create_escrow_contract(&env).client;
+    let escrow = escrow_client.initialize_escrow(
+        &escrow_properties(trustline(&six_decimals_address, 7, "XLM"), 1_000_000),
+        &milestones,
+    );
+    assert!(escrow.trustline == trustline(&six_decimals_address, 6, "USDC6"));
+    assert!(escrow_client.get_escrow().trustline == escrow.trustline);
+
+    let invalid_trustlines = [
+        // Not a token at all
+        trustline(&Address::generate(&env), 7, "USDC"),
+        trustline(&create_escrow_contract(&env).client.address, 7, "USDC"),
+        trustline(&too_precise_address, 19, "DUST"),
+    ];
+    for invalid_trustline in invalid_trustlines {
+        let escrow_client = create_escrow_contract(&env).client;
+        assert_eq!(
+            escrow_client
+                .try_initialize_escrow(
+                    &escrow_properties(invalid_trustline, 1_000_000),
+                    &milestones
+                )
+                .err(),
+            Some(Ok(ContractError::InvalidTrustlineToken))
+        );
+    }
+
+    let escrow_client = create_escrow_contract(&env).client;
+    assert_eq!(
+        escrow_client
+            .try_initialize_escrow(
+                &escrow_properties(token_trustline(&usdc_token.0), -1),
+                &milestones
+            )
+            .err(),
+        Some(Ok(ContractError::AmountCannotBeZero))
+    );
+
+    // The amount cap is in whole tokens, so it follows the token's decimals
+    let six_decimals_cap = MAX_WHOLE_TOKEN_AMOUNT * 1_000_000;
+    let escrow_client = create_escrow_contract(&env).client;
+    assert_eq!(
+        escrow_client
+            .try_initialize_escrow(
+                &escrow_properties(
+                    trustline(&six_decimals_address, 6, "USDC6"),
+                    six_decimals_cap + 1
+                ),
+                &milestones
+            )
+            .err(),
+        Some(Ok(ContractError::AmountTooLarge))
+    );
+    escrow_client.initialize_escrow(
+        &escrow_properties(
+            trustline(&six_decimals_address, 6, "USDC6"),
+            six_decimals_cap,
+        ),
+        &milestones,
+    );
+
+    let usdc_cap = MAX_WHOLE_TOKEN_AMOUNT * 10_000_000;
+    let escrow_client = create_escrow_contract(&env).client;
+    let escrow = escrow_client.initialize_escrow(
+        &escrow_properties(token_trustline(&usdc_token.0), usdc_cap),
+        &milestones,
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_amount(
+                &escrow.roles.platform_address,
+                &usdc_token.0.address,
+                &(usdc_cap + 1)
+            )
+            .err(),
+        Some(Ok(ContractError::AmountTooLarge))
+    );
+}
+
+#[test]
+fn test_token_allowlist_per_platform() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let look_alike_token = create_usdc_token(&env, &admin);
+    let platform_address = Address::generate(&env);
+    let other_platform_address = Address::generate(&env);
+
+    let escrow_properties = |platform_address: &Address, trustline: Trustline| Escrow {
+        roles: Roles {
+            platform_address: platform_address.clone(),
+            ..generate_roles(&env)
+        },
+        ..default_escrow(&env, trustline)
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    let governance = create_escrow_contract(&env);
+    let governance_client = governance.client;
+    let contract_admin = governance.admin;
+    let escrow_client = create_governed_escrow_contract(&env, &governance_client.address);
+
+    assert_eq!(escrow_client.get_token_allowlist(&platform_address), None);
+    // Only the protocol admin manages allowlists, and only on the governance contract
+    assert_eq!(
+        governance_client
+            .try_set_token_allowed(
+                &platform_address,
+                &platform_address,
+                &usdc_token.0.address,
+                &true
+            )
+            .err(),
+        Some(Ok(ContractError::OnlyAdminCanExecuteThisFunction))
+    );
+    assert_eq!(
+        escrow_client
+            .try_set_token_allowed(
+                &contract_admin,
+                &platform_address,
+                &usdc_token.0.address,
+                &true
+            )
+            .err(),
+        Some(Ok(ContractError::NotGovernanceContract))
+    );
+
+    governance_client.set_token_allowed(
+        &contract_admin,
+        &platform_address,
+        &usdc_token.0.address,
+        &true,
+    );
+    let data = find_event_data(&env, "tw_token_allow");
+    assert!(bool::try_from_val(&env, &data.get(0).unwrap()).unwrap());
+    assert_eq!(
+        Address::try_from_val(&env, &data.get(2).unwrap()).unwrap(),
+        usdc_token.0.address
+    );
+
+    // Adding a token twice keeps one entry
+    let allowlist = governance_client.set_token_allowed(
+        &contract_admin,
+        &platform_address,
+        &usdc_token.0.address,
+        &true,
+    );
+    assert_eq!(allowlist, vec![&env, usdc_token.0.address.clone()]);
+    assert_eq!(
+        escrow_client.get_token_allowlist(&platform_address),
+        Some(allowlist)
+    );
+
+    assert_eq!(
+        escrow_client
+            .try_initialize_escrow(
+                &escrow_properties(&platform_address, token_trustline(&look_alike_token.0)),
+                &milestones
+            )
+            .err(),
+        Some(Ok(ContractError::TokenNotAllowed))
+    );
+    // Platforms that have not opted in keep accepting any token
+    let other_client = create_governed_escrow_contract(&env, &governance_client.address);
+    other_client.initialize_escrow(
+        &escrow_properties(
+            &other_platform_address,
+            token_trustline(&look_alike_token.0),
+        ),
+        &milestones,
+    );
+
+    let escrow = escrow_client.initialize_escrow(
+        &escrow_properties(&platform_address, token_trustline(&usdc_token.0)),
+        &milestones,
+    );
+
+    let mut switched_token = escrow.clone();
+    switched_token.trustline = token_trustline(&look_alike_token.0);
+    assert_eq!(
+        escrow_client
+            .try_update_escrow(&platform_address, &switched_token)
+            .err(),
+        Some(Ok(ContractError::TokenNotAllowed))
+    );
+
+    // Emptying the list fails closed instead of opening it to every token
+    let allowlist = governance_client.set_token_allowed(
+        &contract_admin,
+        &platform_address,
+        &usdc_token.0.address,
+        &false,
+    );
+    assert!(allowlist.is_empty());
+    assert_eq!(
+        escrow_client
+            .try_update_escrow(&platform_address, &switched_token)
+            .err(),
+        Some(Ok(ContractError::TokenNotAllowed))
+    );
+
+    governance_client.clear_token_allowlist(&contract_admin, &platform_address);
+    let data = find_event_data(&env, "tw_token_clear");
+    assert_eq!(
+        Address::try_from_val(&env, &data.get(0).unwrap()).unwrap(),
+        platform_address
+    );
+    assert_eq!(escrow_client.get_token_allowlist(&platform_address), None);
+    escrow_client.update_escrow(&platform_address, &switched_token);
+}
+
+#[test]
+fn test_multi_token_escrow_tracks_each_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let usdc_token = create_usdc_token(&env, &admin);
+    let eurc_token = create_usdc_token(&env, &admin);
+    let unrelated_token = create_usdc_token(&env, &admin);
+    let approver_address = Address::generate(&env);
+    let receiver_address = Address::generate(&env);
+    let platform_address = Address::generate(&env);
+    let release_signer_address = Address::generate(&env);
+    let dispute_resolver_address = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+
+    let escrow_properties = |extra_legs: soroban_sdk::Vec<TokenLeg>| Escrow {
+        roles: Roles {
+            approver: approver_address.clone(),
+            platform_address: platform_address.clone(),
+            release_signer: release_signer_address.clone(),
+            dispute_resolver: dispute_resolver_address.clone(),
+            receiver: receiver_address.clone(),
+            ..generate_roles(&env)
+        },
+        extra_legs,
+        ..default_escrow(&env, token_trustline(&usdc_token.0))
+    };
+    let eurc_leg = TokenLeg {
+        trustline: token_trustline(&eurc_token.0),
+        amount: 2_000_000,
+    };
+    let milestones = vec![
+        &env,
+        Milestone {
+            description: String::from_str(&env, "First milestone"),
+            status: String::from_str(&env, "Pending"),
+            evidence: String::from_str(&env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    // Each token may back a single leg
+    let duplicate_leg = TokenLeg {
+        trustline: token_trustline(&usdc_token.0),
+        amount: 2_000_000,
+    };
+    assert_eq!(
+        create_escrow_contract(&env)
+            .client
+            .try_initialize_escrow(&escrow_properties(vec![&env, duplicate_leg]), &milestones)
+            .err(),
+        Some(Ok(ContractError::DuplicateTokenLeg))
+    );
+
+    let mut too_many_legs = vec![&env];
+    for _ in 0..MAX_TOKEN_LEGS {
+        too_many_legs.push_back(TokenLeg {
+            trustline: token_trustline(&create_usdc_token(&env, &admin).0),
+            amount: 1_000_000,
+        });
+    }
+    assert_eq!(
+        create_escrow_contract(&env)
+            .client
+            .try_initialize_escrow(&escrow_properties(too_many_legs), &milestones)
+            .err(),
+        Some(Ok(ContractError::TooManyTokenLegs))
+    );
+
+    let escrow_client = create_escrow_contract(&env).client;
+    escrow_client.initialize_escrow(&escrow_properties(vec![&env, eurc_leg]), &milestones);
+
+    // Every leg's amount can be set, leaving the other legs alone
+    escrow_client.set_amount(&platform_address, &eurc_token.0.address, &3_000_000);
+    let escrow = escrow_client.get_escrow();
+    assert_eq!(escrow.amount, 1_000_000);
+    assert_eq!(escrow.extra_legs.get(0).unwrap().amount, 3_000_000);
+    assert_eq!(
+        escrow_client
+            .try_set_amount(&platform_address, &unrelated_token.0.address, &1_000_000)
+            .err(),
+        Some(Ok(ContractError::TokenNotInEscrow))
+    );
+    escrow_client.set_amount(&platform_address, &eurc_token.0.address, &2_000_000);
+    let escrow = escrow_client.get_escrow();
+
+    usdc_token.1.mint(&approver_address, &1_000_000);
+    eurc_token.1.mint(&approver_address, &2_000_000);
+    unrelated_token.1.mint(&approver_address, &1_000_000);
+    assert_eq!(
+        escrow_client
+            .try_fund_escrow(
+                &approver_address,
+                &escrow,
+                &unrelated_token.0.address,
+                &1_000_000
+            )
+            .err(),
+        Some(Ok(ContractError::TokenNotInEscrow))
+    );
+
+    escrow_client.fund_escrow(
+        &approver_address,
+        &escrow,
+        &usdc_token.0.address,
+        &1_000_000,
+    );
+    let data = find_event_data(&env, "tw_fund");
+    assert_eq!(
+        Address::try_from_val(&env, &data.get(1).unwrap()).unwrap(),
+        usdc_token.0.address
+    );
+    assert_eq!(escrow_client.get_status(), EscrowStatus::PartiallyFunded);
+
+    let funding = escrow_client.get_required_funding();
+    assert_eq!(funding.len(), 2);
+    assert_eq!(funding.get(0).unwrap().total_funded, 1_000_000);
+    let eurc_funding = funding.get(1).unwrap();
+    assert_eq!(eurc_funding.token, eurc_token.0.address);
+    assert_eq!(eurc_funding.required_funding, 2_000_000);
+    assert_eq!(eurc_funding.total_funded, 0);
+
+    escrow_client.fund_escrow(
+        &approver_address,
+        &escrow,
+        &eurc_token.0.address,
+        &2_000_000,
+    );
+    assert_eq!(escrow_client.get_status(), EscrowStatus::Active);
+    assert_eq!(escrow_client.get_escrow_summary().legs.len(), 2);
+
+    // Each leg's amount is checked against its own token's balance
+    assert_eq!(
+        escrow_client
+            .try_set_amount(&platform_address, &eurc_token.0.address, &1_000_000)
+            .err(),
+        Some(Ok(ContractError::EscrowHasFunds))
+    );
+
+    // The fee pipeline runs once per token
+    escrow_client.approve_milestone(&0, &approver_address);
+    let preview = escrow_client.preview_release();
+    assert_eq!(preview.len(), 2);
+    escrow_client.release_funds(&release_signer_address, &trustless_work_address);
+    let payout_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(0)
+                .and_then(|first| Symbol::try_from_val(&env, &first).ok())
+                == Some(Symbol::new(&env, "tw_rel_payout"))
+        })
+        .count();
+    assert_eq!(payout_events, 2);
+
+    for (token, receiver_amount, platform_fee, trustless_work_fee) in [
+        (&usdc_token.0, 967_000, 30_000, 3_000),
+        (&eurc_token.0, 1_934_000, 60_000, 6_000),
+    ] {
+        assert_eq!(token.balance(&receiver_address), receiver_amount);
+        assert_eq!(token.balance(&platform_address), platform_fee);
+        assert_eq!(token.balance(&trustless_work_address), trustless_work_fee);
+        assert_eq!(token.balance(&escrow_client.address), 0);
+    }
+
+    // The audit log records one release entry per token
+    let history_count = escrow_client.get_history_count();
+    let released: std::vec::Vec<(AuditAction, Option<Address>, i128)> = escrow_client
+        .get_history(&(history_count - 2), &2)
+        .iter()
+        .map(|entry| (entry.action, entry.token, entry.amount))
+        .collect();
+    assert_eq!(
+        released,
+        std::vec![
+            (
+                AuditAction::Released,
+                Some(usdc_token.0.address.clone()),
+                1_000_000
+            ),
+            (
+                AuditAction::Released,
+                Some(eurc_token.0.address.clone()),
+                2_000_000
+            ),
+        ]
+    );
+
+    // Disputes distribute every funded leg in its own token
+    let eurc_leg = TokenLeg {
+        trustline: token_trustline(&eurc_token.0),
+        amount: 2_000_000,
+    };
+    let disputed_client = create_escrow_contract(&env).client;
+    disputed_client.initialize_escrow(&escrow_properties(vec![&env, eurc_leg]), &milestones);
+    usdc_token.1.mint(&disputed_client.address, &1_000_000);
+    eurc_token.1.mint(&disputed_client.address, &2_000_000);
+    disputed_client.dispute_escrow(&approver_address);
+
+    let mut usdc_distributions = Map::new(&env);
+    usdc_distributions.set(approver_address.clone(), 1_000_000);
+    let mut eurc_distributions = Map::new(&env);
+    eurc_distributions.set(receiver_address.clone(), 2_000_000);
+
+    let mut distributions = Map::new(&env);
+    distributions.set(usdc_token.0.address.clone(), usdc_distributions.clone());
+    assert_eq!(
+        disputed_client
+            .try_preview_dispute_resolution(&distributions)
+            .err(),
+        Some(Ok(ContractError::DistributionsMustEqualEscrowBalance))
+    );
+    distributions.set(
+        unrelated_token.0.address.clone(),
+        eurc_distributions.clone(),
+    );
+    assert_eq!(
+        disputed_client
+            .try_preview_dispute_resolution(&distributions)
+            .err(),
+        Some(Ok(ContractError::TokenNotInEscrow))
+    );
+
+    distributions.remove(unrelated_token.0.address.clone());
+    distributions.set(eurc_token.0.address.clone(), eurc_distributions);
+    disputed_client.resolve_dispute(
+        &dispute_resolver_address,
+        &trustless_work_address,
+        &distributions,
+    );
+    assert_eq!(usdc_token.0.balance(&approver_address), 967_000);
+    assert_eq!(eurc_token.0.balance(&receiver_address), 2 * 1_934_000);
+    assert_eq!(usdc_token.0.balance(&disputed_client.address), 0);
+    assert_eq!(eurc_token.0.balance(&disputed_client.address), 0);
+}
+
+#[test]
+fn test_error_spec_mirrors_every_contract_error() {
+    let mut mirrored = 0;
+    for code in 1..=100_u32 {
+        let error = Error::from_contract_error(code);
+        let spec_name = match spec::ContractError::try_from(error) {
+            Ok(case) => Some(std::format!("{:?}", case)),
+            Err(_) => spec::ContractErrorExt::try_from(error)
+                .ok()
+                .map(|case| std::format!("{:?}", case)),
+        };
+        let name = ContractError::try_from(error)
+            .ok()
+            .map(|case| std::format!("{:?}", case));
+
+        assert_eq!(spec_name, name, "error code {} is out of step", code);
+        if name.is_some() {
+            mirrored += 1;
+        }
+    }
+    assert!(mirrored > 50);
+}
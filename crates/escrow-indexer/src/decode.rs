@@ -0,0 +1,376 @@
+use stellar_xdr::curr::{
+    ContractEvent, ContractEventBody, ContractEventType, ContractEventV0, Limits, ReadXdr,
+    ScAddress, ScMap, ScVal,
+};
+
+use crate::error::DecodeError;
+use crate::events::*;
+
+/// Version topic published by the contract as the second topic of every event.
+pub const EVENT_SCHEMA_VERSION: &str = "v2";
+
+/// Decodes a base64 `ContractEvent`, as returned by RPC `getEvents` or found in
+/// transaction meta.
+pub fn decode_event_base64(event_xdr: &str) -> Result<DecodedEvent, DecodeError> {
+    let event = ContractEvent::from_xdr_base64(event_xdr, Limits::none())?;
+    decode_event(&event)
+}
+
+pub fn decode_event_xdr(event_xdr: &[u8]) -> Result<DecodedEvent, DecodeError> {
+    let event = ContractEvent::from_xdr(event_xdr, Limits::none())?;
+    decode_event(&event)
+}
+
+pub fn decode_event(event: &ContractEvent) -> Result<DecodedEvent, DecodeError> {
+    if event.type_ != ContractEventType::Contract {
+        return Err(DecodeError::NotAContractEvent);
+    }
+
+    let contract_id = event
+        .contract_id
+        .clone()
+        .ok_or(DecodeError::MissingContractId)?;
+    let ContractEventBody::V0(ContractEventV0 { topics, data }) = &event.body;
+
+    let name = match topics.first() {
+        Some(ScVal::Symbol(name)) => name.0.to_utf8_string_lossy(),
+        Some(_) => return Err(DecodeError::InvalidTopic("name")),
+        None => return Err(DecodeError::MissingTopic("name")),
+    };
+    if !name.starts_with("tw_") {
+        return Err(DecodeError::UnknownEvent(name));
+    }
+    // v1 events were published under their name alone
+    let version = match topics.get(1) {
+        Some(ScVal::Symbol(version)) => version.0.to_utf8_string_lossy(),
+        Some(_) => return Err(DecodeError::InvalidTopic("version")),
+        None => return Err(DecodeError::UnsupportedVersion(String::from("v1"))),
+    };
+    if version != EVENT_SCHEMA_VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+
+    let engagement_id = topics
+        .get(2)
+        .ok_or(DecodeError::MissingTopic("engagement_id"))
+        .and_then(|topic| to_string(topic).ok_or(DecodeError::InvalidTopic("engagement_id")))?;
+    let actor = topics
+        .get(3)
+        .ok_or(DecodeError::MissingTopic("actor"))
+        .and_then(|topic| to_address(topic).ok_or(DecodeError::InvalidTopic("actor")))?;
+
+    Ok(DecodedEvent {
+        contract_id: ScAddress::Contract(contract_id).to_string(),
+        engagement_id,
+        actor,
+        event: decode_data(&name, data)?,
+    })
+}
+
+/// The contract encodes event data as a vec with the fields sorted by name, so each
+/// payload is read back in that order.
+fn decode_data(name: &str, data: &ScVal) -> Result<EscrowEvent, DecodeError> {
+    let mut fields = Fields::new(data)?;
+
+    let event = match name {
+        "tw_init" => EscrowEvent::InitEsc(InitEsc {
+            amount: fields.i128("amount")?,
+            milestone_count: fields.u32("milestone_count")?,
+            token: fields.address("token")?,
+        }),
+        "tw_fund" => EscrowEvent::FundEsc(FundEsc {
+            amount: fields.i128("amount")?,
+            token: fields.address("token")?,
+        }),
+        "tw_release" => EscrowEvent::DisEsc,
+        "tw_rel_payout" => EscrowEvent::ReleasePayout(ReleasePayout {
+            payouts: fields.payouts("payouts")?,
+            platform_address: fields.address("platform_address")?,
+            platform_fee: fields.i128("platform_fee")?,
+            token: fields.address("token")?,
+            trustless_work_address: fields.address("trustless_work_address")?,
+            trustless_work_fee: fields.i128("trustless_work_fee")?,
+        }),
+        "tw_update" => EscrowEvent::ChgEsc,
+        "tw_upd_propose" => EscrowEvent::UpdateProposed(UpdateProposed {
+            expiration_ledger: fields.u32("expiration_ledger")?,
+            required_approvals: fields.addresses("required_approvals")?,
+        }),
+        "tw_upd_approve" => EscrowEvent::UpdateApproved,
+        "tw_title_set" => EscrowEvent::TitleDescriptionSet(TitleDescriptionSet {
+            new_description: fields.string("new_description")?,
+            new_title: fields.string("new_title")?,
+            old_description: fields.string("old_description")?,
+            old_title: fields.string("old_title")?,
+        }),
+        "tw_amount_set" => EscrowEvent::AmountSet(AmountSet {
+            new_amount: fields.i128("new_amount")?,
+            old_amount: fields.i128("old_amount")?,
+            token: fields.address("token")?,
+        }),
+        "tw_ms_add" => EscrowEvent::MilestoneAdded(MilestoneAdded {
+            milestone: fields.milestone("milestone")?,
+            milestone_index: fields.u32("milestone_index")?,
+        }),
+        "tw_ms_remove" => EscrowEvent::MilestoneRemoved(MilestoneRemoved {
+            milestone: fields.milestone("milestone")?,
+            milestone_index: fields.u32("milestone_index")?,
+            moved_from_index: fields.u32("moved_from_index")?,
+        }),
+        "tw_ms_desc" => EscrowEvent::MilestoneDescriptionChanged(MilestoneDescriptionChanged {
+            milestone_index: fields.u32("milestone_index")?,
+            new_description: fields.string("new_description")?,
+            old_description: fields.string("old_description")?,
+        }),
+        "tw_ms_change" => EscrowEvent::MilestoneStatusChanged(MilestoneStatusChanged {
+            milestone_index: fields.u32("milestone_index")?,
+            new_status: fields.string("new_status")?,
+            old_status: fields.string("old_status")?,
+        }),
+        "tw_ms_approve" => EscrowEvent::MilestoneApproved(MilestoneApproved {
+            milestone_index: fields.u32("milestone_index")?,
+        }),
+        "tw_disp_resolve" => EscrowEvent::DisputeResolved,
+        "tw_dis_payout" => EscrowEvent::DisputePayout(DisputePayout {
+            payouts: fields.payouts("payouts")?,
+            platform_address: fields.address("platform_address")?,
+            platform_fee: fields.i128("platform_fee")?,
+            token: fields.address("token")?,
+            trustless_work_address: fields.address("trustless_work_address")?,
+            trustless_work_fee: fields.i128("trustless_work_fee")?,
+        }),
+        "tw_dispute" => EscrowEvent::EscrowDisputed,
+        "tw_role_propose" => EscrowEvent::RoleChangeProposed(RoleChangeProposed {
+            expiration_ledger: fields.u32("expiration_ledger")?,
+            proposers: fields.addresses("proposers")?,
+            role: fields.role("role")?,
+        }),
+        "tw_role_change" => EscrowEvent::RoleChanged(RoleChanged {
+            previous_address: fields.address("previous_address")?,
+            role: fields.role("role")?,
+        }),
+        "tw_ttl_extend" => EscrowEvent::ExtTtlEvt(ExtTtlEvt {
+            ledgers_to_extend: fields.u32("ledgers_to_extend")?,
+        }),
+        "tw_adm_propose" => EscrowEvent::AdminProposed(AdminProposed {
+            new_admin: fields.address("new_admin")?,
+        }),
+        "tw_adm_accept" => EscrowEvent::AdminChanged(AdminChanged {
+            previous_admin: fields.address("previous_admin")?,
+        }),
+        "tw_proto_fee" => EscrowEvent::ProtocolFeeSet(ProtocolFeeSet {
+            fee_bps: fields.u32("fee_bps")?,
+        }),
+        "tw_fee_coll" => EscrowEvent::FeeCollectorSet(FeeCollectorSet {
+            fee_collector: fields.address("fee_collector")?,
+        }),
+        "tw_fee_disc" => EscrowEvent::PlatformFeeDiscountChanged(PlatformFeeDiscountChanged {
+            active: fields.bool("active")?,
+            fee_bps: fields.u32("fee_bps")?,
+            platform: fields.address("platform")?,
+        }),
+        "tw_wasm_allow" => EscrowEvent::WasmAllowlistChanged(WasmAllowlistChanged {
+            allowed: fields.bool("allowed")?,
+            wasm_hash: fields.hash("wasm_hash")?,
+        }),
+        "tw_token_allow" => EscrowEvent::TokenAllowlistChanged(TokenAllowlistChanged {
+            allowed: fields.bool("allowed")?,
+            platform: fields.address("platform")?,
+            token: fields.address("token")?,
+        }),
+        "tw_token_clear" => EscrowEvent::TokenAllowlistCleared(TokenAllowlistCleared {
+            platform: fields.address("platform")?,
+        }),
+        "tw_proto_pause" => EscrowEvent::ProtocolPauseChanged(ProtocolPauseChanged {
+            paused: fields.bool("paused")?,
+        }),
+        "tw_batch_size" => EscrowEvent::MaxBatchSizeSet(MaxBatchSizeSet {
+            max_batch_size: fields.u32("max_batch_size")?,
+        }),
+        "tw_plat_cap" => EscrowEvent::MaxPlatformFeeSet(MaxPlatformFeeSet {
+            max_platform_fee_bps: fields.u32("max_platform_fee_bps")?,
+        }),
+        "tw_min_share" => EscrowEvent::MinReceiverShareSet(MinReceiverShareSet {
+            min_receiver_share_bps: fields.u32("min_receiver_share_bps")?,
+        }),
+        "tw_esc_pause" => EscrowEvent::EscrowPauseChanged(EscrowPauseChanged {
+            paused: fields.bool("paused")?,
+        }),
+        _ => return Err(DecodeError::UnknownEvent(String::from(name))),
+    };
+
+    Ok(event)
+}
+
+////////////////////////
+// Fields /////
+////////////////////////
+
+struct Fields<'a> {
+    values: core::slice::Iter<'a, ScVal>,
+}
+
+impl<'a> Fields<'a> {
+    fn new(data: &'a ScVal) -> Result<Self, DecodeError> {
+        match data {
+            ScVal::Vec(Some(values)) => Ok(Self {
+                values: values.0.iter(),
+            }),
+            ScVal::Vec(None) => Ok(Self { values: [].iter() }),
+            _ => Err(DecodeError::InvalidField("data")),
+        }
+    }
+
+    fn next<T>(
+        &mut self,
+        field: &'static str,
+        convert: impl FnOnce(&ScVal) -> Option<T>,
+    ) -> Result<T, DecodeError> {
+        let value = self.values.next().ok_or(DecodeError::MissingField(field))?;
+        convert(value).ok_or(DecodeError::InvalidField(field))
+    }
+
+    fn address(&mut self, field: &'static str) -> Result<String, DecodeError> {
+        self.next(field, to_address)
+    }
+
+    fn addresses(&mut self, field: &'static str) -> Result<Vec<String>, DecodeError> {
+        self.next(field, |value| {
+            to_vec(value)?.iter().map(to_address).collect()
+        })
+    }
+
+    fn string(&mut self, field: &'static str) -> Result<String, DecodeError> {
+        self.next(field, to_string)
+    }
+
+    fn i128(&mut self, field: &'static str) -> Result<i128, DecodeError> {
+        self.next(field, to_i128)
+    }
+
+    fn u32(&mut self, field: &'static str) -> Result<u32, DecodeError> {
+        self.next(field, to_u32)
+    }
+
+    fn bool(&mut self, field: &'static str) -> Result<bool, DecodeError> {
+        self.next(field, to_bool)
+    }
+
+    fn hash(&mut self, field: &'static str) -> Result<[u8; 32], DecodeError> {
+        self.next(field, |value| match value {
+            ScVal::Bytes(bytes) => bytes.0.as_slice().try_into().ok(),
+            _ => None,
+        })
+    }
+
+    fn role(&mut self, field: &'static str) -> Result<Role, DecodeError> {
+        self.next(field, to_role)
+    }
+
+    fn milestone(&mut self, field: &'static str) -> Result<Milestone, DecodeError> {
+        self.next(field, |value| {
+            let map = to_map(value)?;
+            Some(Milestone {
+                description: to_string(map_get(map, "description")?)?,
+                status: to_string(map_get(map, "status")?)?,
+                evidence: to_string(map_get(map, "evidence")?)?,
+                approved: to_bool(map_get(map, "approved")?)?,
+            })
+        })
+    }
+
+    fn payouts(&mut self, field: &'static str) -> Result<Vec<Payout>, DecodeError> {
+        self.next(field, |value| {
+            to_vec(value)?
+                .iter()
+                .map(|payout| {
+                    let map = to_map(payout)?;
+                    Some(Payout {
+                        recipient: to_address(map_get(map, "recipient")?)?,
+                        amount: to_i128(map_get(map, "amount")?)?,
+                    })
+                })
+                .collect()
+        })
+    }
+}
+
+////////////////////////
+// Values /////
+////////////////////////
+
+fn to_address(value: &ScVal) -> Option<String> {
+    match value {
+        ScVal::Address(address) => Some(address.to_string()),
+        _ => None,
+    }
+}
+
+fn to_string(value: &ScVal) -> Option<String> {
+    match value {
+        ScVal::String(string) => string.0.to_utf8_string().ok(),
+        _ => None,
+    }
+}
+
+fn to_i128(value: &ScVal) -> Option<i128> {
+    match value {
+        ScVal::I128(parts) => Some(parts.into()),
+        _ => None,
+    }
+}
+
+fn to_u32(value: &ScVal) -> Option<u32> {
+    match value {
+        ScVal::U32(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn to_bool(value: &ScVal) -> Option<bool> {
+    match value {
+        ScVal::Bool(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn to_vec(value: &ScVal) -> Option<&[ScVal]> {
+    match value {
+        ScVal::Vec(Some(values)) => Some(values.0.as_slice()),
+        ScVal::Vec(None) => Some(&[]),
+        _ => None,
+    }
+}
+
+fn to_map(value: &ScVal) -> Option<&ScMap> {
+    match value {
+        ScVal::Map(Some(map)) => Some(map),
+        _ => None,
+    }
+}
+
+fn map_get<'a>(map: &'a ScMap, key: &str) -> Option<&'a ScVal> {
+    map.0.iter().find_map(|entry| match &entry.key {
+        ScVal::Symbol(symbol) if symbol.0.as_slice() == key.as_bytes() => Some(&entry.val),
+        _ => None,
+    })
+}
+
+/// Unit enum variants are encoded as a vec holding the variant name.
+fn to_role(value: &ScVal) -> Option<Role> {
+    let variant = match to_vec(value)? {
+        [ScVal::Symbol(variant)] => variant,
+        _ => return None,
+    };
+
+    let role = match variant.0.as_slice() {
+        b"Approver" => Role::Approver,
+        b"ServiceProvider" => Role::ServiceProvider,
+        b"PlatformAddress" => Role::PlatformAddress,
+        b"ReleaseSigner" => Role::ReleaseSigner,
+        b"DisputeResolver" => Role::DisputeResolver,
+        b"Receiver" => Role::Receiver,
+        _ => return None,
+    };
+    Some(role)
+}
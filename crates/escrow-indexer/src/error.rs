@@ -0,0 +1,48 @@
+use core::fmt;
+
+use stellar_xdr::curr as xdr;
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    Xdr(xdr::Error),
+    NotAContractEvent,
+    MissingContractId,
+    MissingTopic(&'static str),
+    InvalidTopic(&'static str),
+    UnsupportedVersion(String),
+    UnknownEvent(String),
+    MissingField(&'static str),
+    InvalidField(&'static str),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Xdr(err) => write!(f, "Invalid XDR: {err}"),
+            DecodeError::NotAContractEvent => {
+                write!(f, "The event was not published by a contract")
+            }
+            DecodeError::MissingContractId => write!(f, "The event has no contract id"),
+            DecodeError::MissingTopic(topic) => write!(f, "The event is missing the {topic} topic"),
+            DecodeError::InvalidTopic(topic) => {
+                write!(f, "The {topic} topic has an unexpected type")
+            }
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported event schema version: {version}")
+            }
+            DecodeError::UnknownEvent(name) => write!(f, "Unknown escrow event: {name}"),
+            DecodeError::MissingField(field) => write!(f, "The event data is missing {field}"),
+            DecodeError::InvalidField(field) => {
+                write!(f, "The {field} field has an unexpected type")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<xdr::Error> for DecodeError {
+    fn from(err: xdr::Error) -> Self {
+        DecodeError::Xdr(err)
+    }
+}
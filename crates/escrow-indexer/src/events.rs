@@ -0,0 +1,279 @@
+//! Typed mirrors of the `tw_*` events in the escrow contract's `events::handler`.
+//!
+//! Addresses are strkeys (`G...`/`C...`) and contract strings are plain `String`s. The
+//! engagement id and actor topics live on [`DecodedEvent`]; each payload only holds the
+//! event's data fields.
+//!
+//! [`Role`] is the contract's own type. [`Milestone`] and [`Payout`] hold host strings and
+//! addresses in the contract, so they are mirrored here with owned values; their `From`
+//! conversions destructure every contract field, so a change to the contract type stops
+//! this crate from compiling until the mirror follows it.
+
+use stellar_xdr::curr::ScAddress;
+
+pub use escrow::Role;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Milestone {
+    pub description: String,
+    pub status: String,
+    pub evidence: String,
+    pub approved: bool,
+}
+
+impl From<&escrow::Milestone> for Milestone {
+    fn from(milestone: &escrow::Milestone) -> Self {
+        let escrow::Milestone {
+            description,
+            status,
+            evidence,
+            approved,
+        } = milestone;
+        Self {
+            description: description.to_string(),
+            status: status.to_string(),
+            evidence: evidence.to_string(),
+            approved: *approved,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payout {
+    pub recipient: String,
+    pub amount: i128,
+}
+
+impl From<&escrow::Payout> for Payout {
+    fn from(payout: &escrow::Payout) -> Self {
+        let escrow::Payout { recipient, amount } = payout;
+        Self {
+            recipient: ScAddress::from(recipient).to_string(),
+            amount: *amount,
+        }
+    }
+}
+
+/// A `tw_*` event together with the topics shared by every event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub contract_id: String,
+    pub engagement_id: String,
+    pub actor: String,
+    pub event: EscrowEvent,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EscrowEvent {
+    InitEsc(InitEsc),
+    FundEsc(FundEsc),
+    DisEsc,
+    ReleasePayout(ReleasePayout),
+    ChgEsc,
+    UpdateProposed(UpdateProposed),
+    UpdateApproved,
+    TitleDescriptionSet(TitleDescriptionSet),
+    AmountSet(AmountSet),
+    MilestoneAdded(MilestoneAdded),
+    MilestoneRemoved(MilestoneRemoved),
+    MilestoneDescriptionChanged(MilestoneDescriptionChanged),
+    MilestoneStatusChanged(MilestoneStatusChanged),
+    MilestoneApproved(MilestoneApproved),
+    DisputeResolved,
+    DisputePayout(DisputePayout),
+    EscrowDisputed,
+    RoleChangeProposed(RoleChangeProposed),
+    RoleChanged(RoleChanged),
+    ExtTtlEvt(ExtTtlEvt),
+    AdminProposed(AdminProposed),
+    AdminChanged(AdminChanged),
+    ProtocolFeeSet(ProtocolFeeSet),
+    FeeCollectorSet(FeeCollectorSet),
+    PlatformFeeDiscountChanged(PlatformFeeDiscountChanged),
+    WasmAllowlistChanged(WasmAllowlistChanged),
+    TokenAllowlistChanged(TokenAllowlistChanged),
+    TokenAllowlistCleared(TokenAllowlistCleared),
+    ProtocolPauseChanged(ProtocolPauseChanged),
+    MaxBatchSizeSet(MaxBatchSizeSet),
+    MaxPlatformFeeSet(MaxPlatformFeeSet),
+    MinReceiverShareSet(MinReceiverShareSet),
+    EscrowPauseChanged(EscrowPauseChanged),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InitEsc {
+    pub amount: i128,
+    pub token: String,
+    pub milestone_count: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FundEsc {
+    pub token: String,
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleasePayout {
+    pub token: String,
+    pub trustless_work_address: String,
+    pub trustless_work_fee: i128,
+    pub platform_address: String,
+    pub platform_fee: i128,
+    pub payouts: Vec<Payout>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpdateProposed {
+    pub required_approvals: Vec<String>,
+    pub expiration_ledger: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TitleDescriptionSet {
+    pub old_title: String,
+    pub new_title: String,
+    pub old_description: String,
+    pub new_description: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AmountSet {
+    pub old_amount: i128,
+    pub new_amount: i128,
+    pub token: String,
+}
+
+// Milestones
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneAdded {
+    pub milestone_index: u32,
+    pub milestone: Milestone,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneRemoved {
+    pub milestone_index: u32,
+    pub milestone: Milestone,
+    pub moved_from_index: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneDescriptionChanged {
+    pub milestone_index: u32,
+    pub old_description: String,
+    pub new_description: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneStatusChanged {
+    pub milestone_index: u32,
+    pub old_status: String,
+    pub new_status: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MilestoneApproved {
+    pub milestone_index: u32,
+}
+
+// Disputes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisputePayout {
+    pub token: String,
+    pub trustless_work_address: String,
+    pub trustless_work_fee: i128,
+    pub platform_address: String,
+    pub platform_fee: i128,
+    pub payouts: Vec<Payout>,
+}
+
+// Roles
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleChangeProposed {
+    pub role: Role,
+    pub proposers: Vec<String>,
+    pub expiration_ledger: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoleChanged {
+    pub role: Role,
+    pub previous_address: String,
+}
+
+// Admin / TTL
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtTtlEvt {
+    pub ledgers_to_extend: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminProposed {
+    pub new_admin: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminChanged {
+    pub previous_admin: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolFeeSet {
+    pub fee_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeCollectorSet {
+    pub fee_collector: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlatformFeeDiscountChanged {
+    pub platform: String,
+    pub fee_bps: u32,
+    pub active: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WasmAllowlistChanged {
+    pub wasm_hash: [u8; 32],
+    pub allowed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenAllowlistChanged {
+    pub platform: String,
+    pub token: String,
+    pub allowed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenAllowlistCleared {
+    pub platform: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProtocolPauseChanged {
+    pub paused: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaxBatchSizeSet {
+    pub max_batch_size: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaxPlatformFeeSet {
+    pub max_platform_fee_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MinReceiverShareSet {
+    pub min_receiver_share_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EscrowPauseChanged {
+    pub paused: bool,
+}
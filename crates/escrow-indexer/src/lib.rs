@@ -0,0 +1,22 @@
+//! Off-chain decoder for the escrow contract's `tw_*` events.
+//!
+//! Decodes `ContractEvent` XDR (schema v2) into typed structs and folds them into an
+//! in-memory projection of each escrow.
+
+mod decode;
+mod error;
+mod events;
+mod projection;
+mod tests {
+    #[cfg(test)]
+    mod test;
+}
+
+pub use crate::decode::{
+    decode_event, decode_event_base64, decode_event_xdr, EVENT_SCHEMA_VERSION,
+};
+pub use crate::error::DecodeError;
+pub use crate::events::*;
+pub use crate::projection::{
+    EscrowProjection, Indexer, ProjectedFlags, ProjectedMilestone, ProjectedTokenLedger,
+};
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+
+use stellar_xdr::curr::ScAddress;
+
+use crate::error::DecodeError;
+use crate::events::{DecodedEvent, EscrowEvent, Payout, Role};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProjectedMilestone {
+    /// `None` until an event carries it; `tw_init` only publishes the milestone count.
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub approved: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProjectedFlags {
+    pub disputed: bool,
+    pub released: bool,
+    pub resolved: bool,
+}
+
+/// Funds one token has moved through the escrow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProjectedTokenLedger {
+    pub total_funded: i128,
+    pub trustless_work_fees: i128,
+    pub platform_fees: i128,
+    /// Net amount paid to each recipient by releases and dispute resolutions.
+    pub payouts: BTreeMap<String, i128>,
+}
+
+impl ProjectedTokenLedger {
+    /// Total moved out of the escrow, fees included.
+    pub fn total_paid_out(&self) -> i128 {
+        self.payouts.values().sum::<i128>() + self.trustless_work_fees + self.platform_fees
+    }
+
+    fn add_payouts(&mut self, payouts: &[Payout]) {
+        for payout in payouts {
+            *self.payouts.entry(payout.recipient.clone()).or_default() += payout.amount;
+        }
+    }
+}
+
+/// Partial view of one escrow contract, rebuilt from its events.
+///
+/// Every event of the escrow keeps `milestones` (apart from the descriptions and statuses
+/// `tw_init` leaves out), `flags`, `ledgers`, `paused` and `pending_update` authoritative.
+/// Events are slim diffs, so `title`, `description`, `amount`, `token` and `roles` are
+/// only known once an event publishes them, and `update_escrow` can rewrite them without
+/// publishing the new values. `stale` marks that case until [`Self::refresh`] is given
+/// the escrow read with `get_escrow`. Fees, payees and the other legs are not projected.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EscrowProjection {
+    pub contract_id: String,
+    pub engagement_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Amount and token of the first leg; `tw_init` doesn't publish the other legs.
+    pub amount: i128,
+    pub token: Option<String>,
+    /// Roles learned from the actor topics and role hand-overs.
+    pub roles: BTreeMap<Role, String>,
+    pub milestones: Vec<ProjectedMilestone>,
+    pub flags: ProjectedFlags,
+    /// Funding and payouts per token address.
+    pub ledgers: BTreeMap<String, ProjectedTokenLedger>,
+    pub paused: bool,
+    /// An update proposal is open. It is dropped by any write to the escrow; one that
+    /// expires unapproved still reads as pending.
+    pub pending_update: bool,
+    /// `title`, `description`, `amount`, `token` or `roles` may be out of date.
+    pub stale: bool,
+    pub events_applied: u32,
+}
+
+impl EscrowProjection {
+    pub fn new(contract_id: String) -> Self {
+        Self {
+            contract_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn ledger(&self, token: &str) -> Option<&ProjectedTokenLedger> {
+        self.ledgers.get(token)
+    }
+
+    /// Overwrites the fields events may leave out of date with the escrow as stored on
+    /// chain, and clears `stale`.
+    pub fn refresh(&mut self, escrow: &escrow::Escrow) {
+        self.engagement_id = escrow.engagement_id.to_string();
+        self.title = Some(escrow.title.to_string());
+        self.description = Some(escrow.description.to_string());
+        self.amount = escrow.amount;
+        self.token = Some(ScAddress::from(&escrow.trustline.address).to_string());
+
+        let roles = &escrow.roles;
+        for (role, address) in [
+            (Role::Approver, &roles.approver),
+            (Role::ServiceProvider, &roles.service_provider),
+            (Role::PlatformAddress, &roles.platform_address),
+            (Role::ReleaseSigner, &roles.release_signer),
+            (Role::DisputeResolver, &roles.dispute_resolver),
+            (Role::Receiver, &roles.receiver),
+        ] {
+            self.roles
+                .insert(role, ScAddress::from(address).to_string());
+        }
+
+        self.flags = ProjectedFlags {
+            disputed: escrow.flags.disputed,
+            released: escrow.flags.released,
+            resolved: escrow.flags.resolved,
+        };
+        self.stale = false;
+    }
+
+    /// Applies an event published by this escrow. Events of other contracts are ignored.
+    pub fn apply(&mut self, event: &DecodedEvent) {
+        if event.contract_id != self.contract_id {
+            return;
+        }
+        if !event.engagement_id.is_empty() {
+            self.engagement_id = event.engagement_id.clone();
+        }
+        let actor = event.actor.clone();
+
+        // The contract drops a pending update whenever it writes the escrow
+        if writes_escrow(&event.event) {
+            self.pending_update = false;
+        }
+
+        match &event.event {
+            EscrowEvent::InitEsc(init) => {
+                self.amount = init.amount;
+                self.token = Some(init.token.clone());
+                self.milestones =
+                    vec![ProjectedMilestone::default(); init.milestone_count as usize];
+                self.roles.insert(Role::PlatformAddress, actor);
+            }
+            EscrowEvent::FundEsc(fund) => {
+                self.ledger_mut(&fund.token).total_funded += fund.amount;
+            }
+            EscrowEvent::DisEsc => {
+                self.flags.released = true;
+                self.roles.insert(Role::ReleaseSigner, actor);
+            }
+            EscrowEvent::ReleasePayout(payout) => {
+                let ledger = self.ledger_mut(&payout.token);
+                ledger.trustless_work_fees += payout.trustless_work_fee;
+                ledger.platform_fees += payout.platform_fee;
+                ledger.add_payouts(&payout.payouts);
+            }
+            EscrowEvent::ChgEsc => self.stale = true,
+            EscrowEvent::UpdateProposed(_) => self.pending_update = true,
+            EscrowEvent::UpdateApproved => {}
+            EscrowEvent::TitleDescriptionSet(change) => {
+                self.title = Some(change.new_title.clone());
+                self.description = Some(change.new_description.clone());
+            }
+            EscrowEvent::AmountSet(change) => {
+                if self.token.as_ref() == Some(&change.token) {
+                    self.amount = change.new_amount;
+                }
+            }
+            EscrowEvent::MilestoneAdded(added) => {
+                let index = (added.milestone_index as usize).min(self.milestones.len());
+                self.milestones.insert(
+                    index,
+                    ProjectedMilestone {
+                        description: Some(added.milestone.description.clone()),
+                        status: Some(added.milestone.status.clone()),
+                        approved: added.milestone.approved,
+                    },
+                );
+            }
+            EscrowEvent::MilestoneRemoved(removed) => {
+                let removed_index = removed.milestone_index as usize;
+                let moved_from_index = removed.moved_from_index as usize;
+                if removed_index < self.milestones.len() && moved_from_index < self.milestones.len()
+                {
+                    self.milestones.swap(removed_index, moved_from_index);
+                    self.milestones.remove(moved_from_index);
+                }
+            }
+            EscrowEvent::MilestoneDescriptionChanged(change) => {
+                if let Some(milestone) = self.milestone_mut(change.milestone_index) {
+                    milestone.description = Some(change.new_description.clone());
+                }
+            }
+            EscrowEvent::MilestoneStatusChanged(change) => {
+                if let Some(milestone) = self.milestone_mut(change.milestone_index) {
+                    milestone.status = Some(change.new_status.clone());
+                }
+                self.roles.insert(Role::ServiceProvider, actor);
+            }
+            EscrowEvent::MilestoneApproved(approval) => {
+                if let Some(milestone) = self.milestone_mut(approval.milestone_index) {
+                    milestone.approved = true;
+                }
+                self.roles.insert(Role::Approver, actor);
+            }
+            EscrowEvent::DisputeResolved => {
+                self.flags.resolved = true;
+                self.flags.disputed = false;
+                self.roles.insert(Role::DisputeResolver, actor);
+            }
+            EscrowEvent::DisputePayout(payout) => {
+                let ledger = self.ledger_mut(&payout.token);
+                ledger.trustless_work_fees += payout.trustless_work_fee;
+                ledger.platform_fees += payout.platform_fee;
+                ledger.add_payouts(&payout.payouts);
+            }
+            EscrowEvent::EscrowDisputed => self.flags.disputed = true,
+            EscrowEvent::RoleChanged(change) => {
+                self.roles.insert(change.role, actor);
+            }
+            EscrowEvent::EscrowPauseChanged(change) => self.paused = change.paused,
+            // Proposals and contract-level admin settings don't change the escrow itself
+            EscrowEvent::RoleChangeProposed(_)
+            | EscrowEvent::ExtTtlEvt(_)
+            | EscrowEvent::AdminProposed(_)
+            | EscrowEvent::AdminChanged(_)
+            | EscrowEvent::ProtocolFeeSet(_)
+            | EscrowEvent::FeeCollectorSet(_)
+            | EscrowEvent::PlatformFeeDiscountChanged(_)
+            | EscrowEvent::WasmAllowlistChanged(_)
+            | EscrowEvent::TokenAllowlistChanged(_)
+            | EscrowEvent::TokenAllowlistCleared(_)
+            | EscrowEvent::ProtocolPauseChanged(_)
+            | EscrowEvent::MaxBatchSizeSet(_)
+            | EscrowEvent::MaxPlatformFeeSet(_)
+            | EscrowEvent::MinReceiverShareSet(_) => {}
+        }
+
+        self.events_applied += 1;
+    }
+
+    fn ledger_mut(&mut self, token: &str) -> &mut ProjectedTokenLedger {
+        self.ledgers.entry(token.to_string()).or_default()
+    }
+
+    fn milestone_mut(&mut self, milestone_index: u32) -> Option<&mut ProjectedMilestone> {
+        self.milestones.get_mut(milestone_index as usize)
+    }
+}
+
+/// Events the contract publishes after writing the escrow itself.
+fn writes_escrow(event: &EscrowEvent) -> bool {
+    matches!(
+        event,
+        EscrowEvent::InitEsc(_)
+            | EscrowEvent::DisEsc
+            | EscrowEvent::ChgEsc
+            | EscrowEvent::TitleDescriptionSet(_)
+            | EscrowEvent::AmountSet(_)
+            | EscrowEvent::EscrowDisputed
+            | EscrowEvent::DisputeResolved
+            | EscrowEvent::RoleChanged(_)
+    )
+}
+
+/// Folds events from any number of escrow contracts into one projection per contract.
+#[derive(Clone, Debug, Default)]
+pub struct Indexer {
+    escrows: BTreeMap<String, EscrowProjection>,
+}
+
+impl Indexer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn apply(&mut self, event: &DecodedEvent) {
+        self.escrows
+            .entry(event.contract_id.clone())
+            .or_insert_with(|| EscrowProjection::new(event.contract_id.clone()))
+            .apply(event);
+    }
+
+    /// Decodes a base64 `ContractEvent` and applies it. Nothing is applied on error.
+    pub fn ingest_base64(&mut self, event_xdr: &str) -> Result<DecodedEvent, DecodeError> {
+        let event = crate::decode::decode_event_base64(event_xdr)?;
+        self.apply(&event);
+        Ok(event)
+    }
+
+    /// Refreshes the projection of `contract_id` with the escrow read from it.
+    pub fn refresh(&mut self, contract_id: &str, escrow: &escrow::Escrow) {
+        self.escrows
+            .entry(contract_id.to_string())
+            .or_insert_with(|| EscrowProjection::new(contract_id.to_string()))
+            .refresh(escrow);
+    }
+
+    pub fn escrow(&self, contract_id: &str) -> Option<&EscrowProjection> {
+        self.escrows.get(contract_id)
+    }
+
+    pub fn escrows(&self) -> impl Iterator<Item = &EscrowProjection> {
+        self.escrows.values()
+    }
+}
@@ -0,0 +1,511 @@
+#![cfg(test)]
+
+use escrow::{
+    EscrowContract, EscrowContractClient, FeeMode, FeeSchedule, Flags, Governance,
+    Milestone as ContractMilestone, Roles, Trustline,
+};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token, vec, Address, Env, Map, String as SorobanString, TryFromVal,
+};
+use stellar_xdr::curr::{
+    ContractEvent, ContractEventBody, ContractEventType, ContractEventV0, ExtensionPoint, Limits,
+    ScAddress, ScString, ScSymbol, ScVal, WriteXdr,
+};
+
+use crate::{
+    decode_event_base64, decode_event_xdr, DecodeError, EscrowEvent, Indexer, Milestone,
+    MilestoneRemoved, MilestoneStatusChanged, Payout, ProjectedMilestone, Role,
+};
+
+/// Re-encodes the events of the last invocation as the `ContractEvent` XDR an RPC node
+/// would return, token transfers included.
+fn capture(env: &Env, captured: &mut Vec<std::string::String>) {
+    for (contract, topics, data) in env.events().all().iter() {
+        let ScAddress::Contract(contract_id) = ScAddress::from(&contract) else {
+            panic!("events are published by contracts");
+        };
+        let topics: Vec<ScVal> = topics
+            .iter()
+            .map(|topic| ScVal::try_from_val(env, &topic).unwrap())
+            .collect();
+        let event = ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(contract_id),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: topics.try_into().unwrap(),
+                data: ScVal::try_from_val(env, &data).unwrap(),
+            }),
+        };
+        captured.push(event.to_xdr_base64(Limits::none()).unwrap());
+    }
+}
+
+fn strkey(address: &Address) -> std::string::String {
+    ScAddress::from(address).to_string()
+}
+
+struct Setup<'a> {
+    client: EscrowContractClient<'a>,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    roles: Roles,
+    escrow: escrow::Escrow,
+    milestones: soroban_sdk::Vec<ContractMilestone>,
+}
+
+fn setup<'a>(env: &'a Env, engagement_id: &str) -> Setup<'a> {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = token::Client::new(env, &sac.address());
+    let client = EscrowContractClient::new(
+        env,
+        &env.register(EscrowContract {}, (Governance::Admin(admin),)),
+    );
+
+    let service_provider = Address::generate(env);
+    let roles = Roles {
+        approver: Address::generate(env),
+        service_provider: service_provider.clone(),
+        platform_address: Address::generate(env),
+        release_signer: Address::generate(env),
+        dispute_resolver: Address::generate(env),
+        receiver: service_provider,
+    };
+    let escrow = escrow::Escrow {
+        engagement_id: SorobanString::from_str(env, engagement_id),
+        title: SorobanString::from_str(env, "Indexer Escrow"),
+        description: SorobanString::from_str(env, "Indexer Escrow Description"),
+        roles: roles.clone(),
+        amount: 100_000_000,
+        platform_fee: 5 * 100,
+        flags: Flags {
+            disputed: false,
+            released: false,
+            resolved: false,
+        },
+        trustline: Trustline {
+            address: sac.address(),
+            decimals: token_client.decimals(),
+            symbol: token_client.symbol(),
+        },
+        fee_mode: FeeMode::DeductFromReceiver,
+        fee_schedule: FeeSchedule::Flat,
+        receiver_memo: 0,
+        payees: vec![env],
+        extra_legs: vec![env],
+    };
+    let milestones = vec![
+        env,
+        ContractMilestone {
+            description: SorobanString::from_str(env, "First milestone"),
+            status: SorobanString::from_str(env, "Pending"),
+            evidence: SorobanString::from_str(env, "Initial evidence"),
+            approved: false,
+        },
+        ContractMilestone {
+            description: SorobanString::from_str(env, "Second milestone"),
+            status: SorobanString::from_str(env, "Pending"),
+            evidence: SorobanString::from_str(env, "Initial evidence"),
+            approved: false,
+        },
+    ];
+
+    Setup {
+        client,
+        token: token_client,
+        token_admin: token::StellarAssetClient::new(env, &sac.address()),
+        roles,
+        escrow,
+        milestones,
+    }
+}
+
+#[test]
+fn test_release_lifecycle_is_projected_from_captured_events() {
+    let env = Env::default();
+    let setup = setup(&env, "indexer_release");
+    let client = &setup.client;
+    let roles = &setup.roles;
+    let funder = Address::generate(&env);
+    let trustless_work_address = Address::generate(&env);
+    let mut captured = Vec::new();
+
+    let escrow = client.initialize_escrow(&setup.escrow, &setup.milestones);
+    capture(&env, &mut captured);
+
+    setup.token_admin.mint(&funder, &100_000_000);
+    client.fund_escrow(&funder, &escrow, &setup.token.address, &60_000_000);
+    capture(&env, &mut captured);
+    client.fund_escrow(&funder, &escrow, &setup.token.address, &40_000_000);
+    capture(&env, &mut captured);
+
+    client.set_title_description(
+        &roles.platform_address,
+        &SorobanString::from_str(&env, "Renamed Escrow"),
+        &SorobanString::from_str(&env, "Renamed Description"),
+    );
+    capture(&env, &mut captured);
+
+    for milestone_index in 0..2 {
+        client.change_milestone_status(
+            &milestone_index,
+            &SorobanString::from_str(&env, "Completed"),
+            &None,
+            &roles.service_provider,
+        );
+        capture(&env, &mut captured);
+        client.approve_milestone(&milestone_index, &roles.approver);
+        capture(&env, &mut captured);
+    }
+
+    client.release_funds(&roles.release_signer, &trustless_work_address);
+    capture(&env, &mut captured);
+
+    let mut indexer = Indexer::new();
+    let decoded: Vec<_> = captured
+        .iter()
+        .filter_map(|event_xdr| match indexer.ingest_base64(event_xdr) {
+            Ok(event) => Some(event),
+            // Token transfers share the transaction but aren't escrow events
+            Err(DecodeError::UnknownEvent(_)) => None,
+            Err(err) => panic!("{err}"),
+        })
+        .collect();
+    assert_eq!(decoded.len(), 10);
+
+    // Every event carries the engagement and the acting party as topics
+    let status_change = &decoded[4];
+    assert_eq!(status_change.engagement_id, "indexer_release");
+    assert_eq!(status_change.actor, strkey(&roles.service_provider));
+    assert_eq!(
+        status_change.event,
+        EscrowEvent::MilestoneStatusChanged(MilestoneStatusChanged {
+            milestone_index: 0,
+            old_status: "Pending".into(),
+            new_status: "Completed".into(),
+        })
+    );
+
+    let projection = indexer.escrow(&strkey(&client.address)).unwrap();
+    assert_eq!(projection.engagement_id, "indexer_release");
+    assert_eq!(projection.title.as_deref(), Some("Renamed Escrow"));
+    assert_eq!(
+        projection.description.as_deref(),
+        Some("Renamed Description")
+    );
+    assert_eq!(projection.amount, 100_000_000);
+    assert_eq!(projection.token, Some(strkey(&setup.token.address)));
+    let ledger = projection.ledger(&strkey(&setup.token.address)).unwrap();
+    assert_eq!(ledger.total_funded, 100_000_000);
+    assert_eq!(
+        projection.milestones,
+        std::vec![
+            ProjectedMilestone {
+                description: None,
+                status: Some("Completed".into()),
+                approved: true,
+            };
+            2
+        ]
+    );
+    assert!(projection.flags.released);
+    assert!(!projection.flags.disputed);
+    assert_eq!(projection.events_applied, 10);
+
+    // The projection agrees with the contract and the token ledger
+    let on_chain = client.get_escrow();
+    assert_eq!(projection.flags.released, on_chain.flags.released);
+    assert_eq!(
+        ledger.payouts.get(&strkey(&roles.receiver)).copied(),
+        Some(setup.token.balance(&roles.receiver))
+    );
+    assert_eq!(
+        ledger.platform_fees,
+        setup.token.balance(&roles.platform_address)
+    );
+    assert_eq!(
+        ledger.trustless_work_fees,
+        setup.token.balance(&trustless_work_address)
+    );
+    assert_eq!(
+        ledger.total_funded - ledger.total_paid_out(),
+        setup.token.balance(&client.address)
+    );
+
+    for (role, address) in [
+        (Role::PlatformAddress, &roles.platform_address),
+        (Role::ServiceProvider, &roles.service_provider),
+        (Role::Approver, &roles.approver),
+        (Role::ReleaseSigner, &roles.release_signer),
+    ] {
+        assert_eq!(projection.roles.get(&role), Some(&strkey(address)));
+    }
+    // A payout recipient is not necessarily the receiver, so no event names it here
+    assert_eq!(projection.roles.get(&Role::Receiver), None);
+}
+
+#[test]
+fn test_projection_tracks_pending_updates_and_staleness() {
+    let env = Env::default();
+    let setup = setup(&env, "indexer_updates");
+    let client = &setup.client;
+    let roles = &setup.roles;
+    let contract_id = strkey(&client.address);
+    let mut indexer = Indexer::new();
+    let apply_last_invocation = |indexer: &mut Indexer| {
+        let mut captured = Vec::new();
+        capture(&env, &mut captured);
+        for event_xdr in &captured {
+            indexer.ingest_base64(event_xdr).unwrap();
+        }
+    };
+
+    client.initialize_escrow(&setup.escrow, &setup.milestones);
+    apply_last_invocation(&mut indexer);
+    let bigger_escrow = escrow::Escrow {
+        amount: setup.escrow.amount * 2,
+        ..setup.escrow.clone()
+    };
+
+    // Any write to the escrow drops the open proposal
+    client.update_escrow(&roles.platform_address, &bigger_escrow);
+    apply_last_invocation(&mut indexer);
+    assert!(indexer.escrow(&contract_id).unwrap().pending_update);
+
+    client.set_title_description(
+        &roles.platform_address,
+        &SorobanString::from_str(&env, "Renamed Escrow"),
+        &setup.escrow.description,
+    );
+    apply_last_invocation(&mut indexer);
+    assert!(client.get_pending_update().is_none());
+    let projection = indexer.escrow(&contract_id).unwrap();
+    assert!(!projection.pending_update);
+    assert!(!projection.stale);
+
+    // An applied proposal rewrites fields no event publishes
+    client.update_escrow(&roles.platform_address, &bigger_escrow);
+    apply_last_invocation(&mut indexer);
+    client.approve_escrow_update(&roles.approver);
+    apply_last_invocation(&mut indexer);
+    assert!(indexer.escrow(&contract_id).unwrap().pending_update);
+    client.approve_escrow_update(&roles.service_provider);
+    apply_last_invocation(&mut indexer);
+
+    let projection = indexer.escrow(&contract_id).unwrap();
+    assert!(!projection.pending_update);
+    assert!(projection.stale);
+    assert_eq!(projection.amount, setup.escrow.amount);
+    assert_eq!(projection.title.as_deref(), Some("Renamed Escrow"));
+
+    // Reading the escrow back brings the projection up to date
+    indexer.refresh(&contract_id, &client.get_escrow());
+    let projection = indexer.escrow(&contract_id).unwrap();
+    assert!(!projection.stale);
+    assert_eq!(projection.amount, bigger_escrow.amount);
+    assert_eq!(projection.title.as_deref(), Some("Indexer Escrow"));
+    assert_eq!(
+        projection.roles.get(&Role::Receiver),
+        Some(&strkey(&roles.receiver))
+    );
+}
+
+#[test]
+fn test_dispute_and_role_events_are_decoded_and_projected() {
+    let env = Env::default();
+    let setup = setup(&env, "indexer_dispute");
+    let client = &setup.client;
+    let roles = &setup.roles;
+    let trustless_work_address = Address::generate(&env);
+    let new_approver = Address::generate(&env);
+    let mut captured = Vec::new();
+
+    client.initialize_escrow(&setup.escrow, &setup.milestones);
+    capture(&env, &mut captured);
+    setup.token_admin.mint(&client.address, &100_000_000);
+
+    let third_milestone = ContractMilestone {
+        description: SorobanString::from_str(&env, "Third milestone"),
+        status: SorobanString::from_str(&env, "Pending"),
+        evidence: SorobanString::from_str(&env, ""),
+        approved: false,
+    };
+    client.add_milestone(&roles.platform_address, &third_milestone);
+    capture(&env, &mut captured);
+    // The last milestone moves into the freed index
+    client.remove_milestone(&roles.platform_address, &0);
+    capture(&env, &mut captured);
+
+    client.propose_role_change(
+        &vec![&env, roles.approver.clone()],
+        &Role::Approver,
+        &new_approver,
+    );
+    capture(&env, &mut captured);
+    client.accept_role_change(&Role::Approver, &new_approver);
+    capture(&env, &mut captured);
+
+    client.dispute_escrow(&new_approver);
+    capture(&env, &mut captured);
+
+    let mut token_distributions = Map::new(&env);
+    token_distributions.set(new_approver.clone(), 70_000_000);
+    token_distributions.set(roles.service_provider.clone(), 30_000_000);
+    let mut distributions = Map::new(&env);
+    distributions.set(setup.token.address.clone(), token_distributions);
+    let preview = client
+        .preview_dispute_resolution(&distributions)
+        .get(0)
+        .unwrap();
+    client.resolve_dispute(
+        &roles.dispute_resolver,
+        &trustless_work_address,
+        &distributions,
+    );
+    capture(&env, &mut captured);
+
+    let decoded: Vec<_> = captured
+        .iter()
+        .filter_map(|event_xdr| match decode_event_base64(event_xdr) {
+            Err(DecodeError::UnknownEvent(_)) => None,
+            event => Some(event.unwrap()),
+        })
+        .collect();
+    assert!(
+        matches!(decoded[1].event, EscrowEvent::MilestoneAdded(ref added)
+        if added.milestone_index == 2
+            && added.milestone == Milestone::from(&third_milestone))
+    );
+    assert_eq!(
+        decoded[2].event,
+        EscrowEvent::MilestoneRemoved(MilestoneRemoved {
+            milestone_index: 0,
+            milestone: Milestone::from(&setup.milestones.get(0).unwrap()),
+            moved_from_index: 2,
+        })
+    );
+    assert!(
+        matches!(decoded[3].event, EscrowEvent::RoleChangeProposed(ref proposal)
+        if proposal.role == Role::Approver
+            && proposal.proposers == std::vec![strkey(&roles.approver)])
+    );
+    assert!(
+        matches!(decoded[4].event, EscrowEvent::RoleChanged(ref change)
+        if change.role == Role::Approver && change.previous_address == strkey(&roles.approver))
+    );
+    assert_eq!(decoded[5].event, EscrowEvent::EscrowDisputed);
+
+    let EscrowEvent::DisputePayout(ref payout) = decoded[6].event else {
+        panic!("expected tw_dis_payout, got {:?}", decoded[6].event);
+    };
+    let expected: Vec<Payout> = preview
+        .payouts
+        .iter()
+        .map(|payout| Payout::from(&payout))
+        .collect();
+    assert_eq!(payout.payouts, expected);
+    assert_eq!(payout.platform_fee, preview.platform_fee);
+    assert_eq!(payout.trustless_work_fee, preview.trustless_work_fee);
+    assert_eq!(decoded[7].event, EscrowEvent::DisputeResolved);
+
+    let mut indexer = Indexer::new();
+    decoded.iter().for_each(|event| indexer.apply(event));
+    let projection = indexer.escrow(&strkey(&client.address)).unwrap();
+    assert_eq!(projection.milestones.len(), 2);
+    assert_eq!(
+        projection.milestones[0].description.as_deref(),
+        Some("Third milestone")
+    );
+    assert_eq!(projection.milestones[1].description, None);
+    assert_eq!(
+        projection.roles.get(&Role::Approver),
+        Some(&strkey(&new_approver))
+    );
+    assert!(projection.flags.resolved);
+    assert!(!projection.flags.disputed);
+    let ledger = projection.ledger(&strkey(&setup.token.address)).unwrap();
+    assert_eq!(
+        ledger.payouts.get(&strkey(&new_approver)).copied(),
+        Some(setup.token.balance(&new_approver))
+    );
+    assert_eq!(
+        ledger
+            .payouts
+            .get(&strkey(&roles.service_provider))
+            .copied(),
+        Some(setup.token.balance(&roles.service_provider))
+    );
+    assert_eq!(indexer.escrows().count(), 1);
+}
+
+#[test]
+fn test_decoder_rejects_foreign_and_v1_events() {
+    let contract_id = match "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAOLZM"
+        .parse::<ScAddress>()
+        .unwrap()
+    {
+        ScAddress::Contract(contract_id) => contract_id,
+        _ => unreachable!(),
+    };
+    let event = |topics: std::vec::Vec<ScVal>| {
+        ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(contract_id.clone()),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: topics.try_into().unwrap(),
+                data: ScVal::Vec(None),
+            }),
+        }
+        .to_xdr(Limits::none())
+        .unwrap()
+    };
+    let symbol = |name: &str| ScVal::Symbol(ScSymbol(name.try_into().unwrap()));
+
+    assert_eq!(
+        decode_event_xdr(&event(std::vec![symbol("tw_fund")])),
+        Err(DecodeError::UnsupportedVersion("v1".into()))
+    );
+    assert_eq!(
+        decode_event_xdr(&event(std::vec![symbol("tw_fund"), symbol("v3")])),
+        Err(DecodeError::UnsupportedVersion("v3".into()))
+    );
+    assert_eq!(
+        decode_event_xdr(&event(std::vec![symbol("tw_fund"), symbol("v2")])),
+        Err(DecodeError::MissingTopic("engagement_id"))
+    );
+    assert_eq!(
+        decode_event_xdr(&event(std::vec![symbol("transfer")])),
+        Err(DecodeError::UnknownEvent("transfer".into()))
+    );
+
+    let address = ScVal::Address(ScAddress::Contract(contract_id.clone()));
+    let engagement_id = ScVal::String(ScString("engagement".try_into().unwrap()));
+    assert_eq!(
+        decode_event_xdr(&event(std::vec![
+            symbol("tw_unknown"),
+            symbol("v2"),
+            engagement_id.clone(),
+            address.clone(),
+        ])),
+        Err(DecodeError::UnknownEvent("tw_unknown".into()))
+    );
+    // A v2 event whose data lacks a field is rejected rather than half decoded
+    assert_eq!(
+        decode_event_xdr(&event(std::vec![
+            symbol("tw_fund"),
+            symbol("v2"),
+            engagement_id,
+            address,
+        ])),
+        Err(DecodeError::MissingField("amount"))
+    );
+    assert!(matches!(
+        decode_event_base64("not xdr"),
+        Err(DecodeError::Xdr(_))
+    ));
+}